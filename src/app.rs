@@ -1,7 +1,8 @@
 use std::time::{Duration, Instant};
 
-use anyhow::{Ok, Result};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
+use anyhow::Result;
+use chrono::Utc;
+use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::{
     layout::{Constraint, Layout},
     prelude::CrosstermBackend,
@@ -10,23 +11,85 @@ use ratatui::{
 };
 
 use crate::{
+    clipboard,
+    config::Config,
     event::{Event, EventHandler},
+    export::ExportFormat,
+    geolocation::Geolocator,
+    keymap::{Action, Keymap},
     tui::Tui,
     widgets::{
+        geo_belt::{self, GeoBelt, GeoBeltState},
+        help::{self, Help, HelpState},
+        legend::{self, Legend, LegendState},
         object_information::{self, ObjectInformation, ObjectInformationState},
         satellites::{self, Satellites, SatellitesState},
-        world_map::{self, WorldMap, WorldMapState},
+        sky::Sky,
+        toast::{Toast, ToastState},
+        world_map::{self, MapMode, Measurement, RasterMap, Viewport, WorldMap, WorldMapState},
     },
 };
 
+/// The view shown in the main area.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub enum View {
+    #[default]
+    WorldMap,
+    /// Longitudes and drift rates of the geostationary objects.
+    GeoBelt,
+}
+
+/// The panel receiving the keys that are not handled globally.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub enum Focus {
+    /// The main area, showing the current [`View`].
+    #[default]
+    Main,
+    ObjectInformation,
+    Satellites,
+}
+
+impl Focus {
+    /// Returns the panel after this one, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Main => Self::ObjectInformation,
+            Self::ObjectInformation => Self::Satellites,
+            Self::Satellites => Self::Main,
+        }
+    }
+
+    /// Returns the panel before this one, wrapping around.
+    pub fn previous(self) -> Self {
+        match self {
+            Self::Main => Self::Satellites,
+            Self::ObjectInformation => Self::Main,
+            Self::Satellites => Self::ObjectInformation,
+        }
+    }
+}
+
 /// Application.
 pub struct App {
     /// Indicates if the application is currently active and running. When set to false, triggers application shutdown.
     pub running: bool,
 
+    pub view: View,
+    pub focus: Focus,
+    /// Whether the key bindings are shown.
+    pub show_help: bool,
+    pub help_state: HelpState,
+
     pub world_map_state: WorldMapState,
+    pub geo_belt_state: GeoBeltState,
     pub satellites_state: SatellitesState,
     pub object_information_state: ObjectInformationState,
+    pub legend_state: LegendState,
+    pub toast_state: ToastState,
+
+    pub config: Config,
+    pub keymap: Keymap,
+    pub geolocator: Geolocator,
 
     tui: Tui<CrosstermBackend<std::io::Stdout>>,
 }
@@ -34,22 +97,34 @@ pub struct App {
 impl App {
     /// Constructs a new instance of [`App`].
     pub fn new() -> Result<Self> {
+        let config = Config::load()?;
         let backend = CrosstermBackend::new(std::io::stdout());
         let terminal = Terminal::new(backend)?;
         let events = EventHandler::new();
         let tui = Tui::new(terminal, events);
         Ok(Self {
             running: true,
+            view: Default::default(),
+            focus: Default::default(),
+            show_help: false,
+            help_state: Default::default(),
             world_map_state: Default::default(),
+            geo_belt_state: Default::default(),
             satellites_state: Default::default(),
             object_information_state: Default::default(),
+            legend_state: LegendState::new(config.color_by),
+            toast_state: Default::default(),
+            keymap: config.keymap()?,
+            config,
+            geolocator: Geolocator::new(),
             tui,
         })
     }
 
     /// Runs the main loop of the application.
     pub async fn run(&mut self) -> Result<()> {
-        self.tui.init()?;
+        self.tui.init(self.config.graphics)?;
+        self.world_map_state.raster = self.tui.picker.map(RasterMap::new);
 
         // Start the main loop.
         while self.running {
@@ -67,22 +142,60 @@ impl App {
 
     /// Renders the terminal interface.
     pub fn render(&mut self) -> Result<()> {
+        const TRAJECTORY_COLORS: [Color; 6] = [
+            Color::LightBlue,
+            Color::LightMagenta,
+            Color::LightYellow,
+            Color::LightCyan,
+            Color::LightGreen,
+            Color::LightRed,
+        ];
+
         self.tui.terminal.draw(|frame| {
             let horizontal = Layout::horizontal([Constraint::Percentage(80), Constraint::Min(25)]);
             let [left, right] = horizontal.areas(frame.area());
-            let vertical = Layout::vertical([Constraint::Percentage(60), Constraint::Fill(1)]);
-            let [top_right, bottom_right] = vertical.areas(right);
+            let vertical = Layout::vertical([
+                Constraint::Percentage(50),
+                Constraint::Percentage(20),
+                Constraint::Length(5),
+                Constraint::Fill(1),
+            ]);
+            let [top_right, middle_right, sky_area, bottom_right] = vertical.areas(right);
 
             let world_map = WorldMap {
                 satellites_state: &self.satellites_state,
+                legend_state: &self.legend_state,
+                ground_stations: &self.config.ground_stations,
+                observer: self.config.observer(),
+                watch_list: &self.config.watch_list,
                 satellit_symbol: "+".to_string(),
-                trajectory_color: Color::LightBlue,
+                trajectory_colors: &TRAJECTORY_COLORS,
+                focused: self.focus == Focus::Main,
             };
-            frame.render_stateful_widget(world_map, left, &mut self.world_map_state);
+            match self.view {
+                View::WorldMap => {
+                    frame.render_stateful_widget(world_map, left, &mut self.world_map_state)
+                }
+                View::GeoBelt => {
+                    let geo_belt = GeoBelt {
+                        satellites_state: &self.satellites_state,
+                        world_map_state: &self.world_map_state,
+                        legend_state: &self.legend_state,
+                        focused: self.focus == Focus::Main,
+                    };
+                    frame.render_stateful_widget(geo_belt, left, &mut self.geo_belt_state);
+                }
+            }
 
             let object_information = ObjectInformation {
                 satellites_state: &self.satellites_state,
                 world_map_state: &self.world_map_state,
+                geolocator: &self.geolocator,
+                trajectory_colors: &TRAJECTORY_COLORS,
+                fields: self.config.information_fields(),
+                units: &self.config.units,
+                observer: self.config.observer(),
+                focused: self.focus == Focus::ObjectInformation,
             };
             frame.render_stateful_widget(
                 object_information,
@@ -90,7 +203,23 @@ impl App {
                 &mut self.object_information_state,
             );
 
-            frame.render_stateful_widget(Satellites, bottom_right, &mut self.satellites_state);
+            frame.render_stateful_widget(Legend, middle_right, &mut self.legend_state);
+            let sky = Sky {
+                time: self.satellites_state.time,
+                observer: self.config.observer(),
+            };
+            frame.render_widget(sky, sky_area);
+            let satellites = Satellites {
+                focused: self.focus == Focus::Satellites,
+            };
+            frame.render_stateful_widget(satellites, bottom_right, &mut self.satellites_state);
+            if self.show_help {
+                let help = Help {
+                    keymap: &self.keymap,
+                };
+                frame.render_stateful_widget(help, frame.area(), &mut self.help_state);
+            }
+            frame.render_stateful_widget(Toast, frame.area(), &mut self.toast_state);
         })?;
         Ok(())
     }
@@ -101,37 +230,138 @@ impl App {
 
         let now = Instant::now();
         if now.duration_since(self.satellites_state.last_object_update) >= OBJECT_UPDATE_INTERVAL {
+            // The reloaded objects may be in a different order, or some may be gone
+            let selected = self
+                .world_map_state
+                .selected_norad_ids(&self.satellites_state.objects);
             self.satellites_state.refresh_objects().await;
+            self.world_map_state
+                .reselect(&selected, &self.satellites_state.objects);
             self.satellites_state.last_object_update = now;
         }
+
+        self.satellites_state.update_states(Utc::now());
+        self.legend_state
+            .update(&self.satellites_state.objects, self.satellites_state.time);
     }
 
     /// Set running to false to quit the application.
     pub fn quit(&mut self) {
         self.running = false;
     }
+
+    /// Copies the text to the clipboard, notifying whether `what` was copied.
+    pub fn copy_to_clipboard(&mut self, text: &str, what: &str) {
+        match clipboard::copy(text) {
+            Ok(backend) => self
+                .toast_state
+                .info(format!("Copied {} to {}", what, backend)),
+            Err(err) => self
+                .toast_state
+                .error(format!("Failed to copy {}: {}", what, err)),
+        }
+    }
+
+    /// Copies the most recently selected object to the clipboard in the given format.
+    fn copy_selected_object(&mut self, format: ExportFormat) {
+        let Some(index) = self.world_map_state.selected_object() else {
+            self.toast_state.error("No object selected");
+            return;
+        };
+        let object = &self.satellites_state.objects[index];
+        match format.export(object, self.satellites_state.time) {
+            Ok(text) => self.copy_to_clipboard(&text, &format.to_string()),
+            Err(err) => self
+                .toast_state
+                .error(format!("Failed to export {}: {}", format, err)),
+        }
+    }
 }
 
 async fn handle_key_events(event: KeyEvent, app: &mut App) -> Result<()> {
-    match event.code {
-        // Exit application on `ESC`
-        KeyCode::Esc => {
+    if app.show_help {
+        return help::handle_key_events(event, app).await;
+    }
+    let Some(action) = app.keymap.action(event) else {
+        return Ok(());
+    };
+    match action {
+        Action::Quit => {
             app.quit();
         }
-        // Exit application on `Ctrl-C`
-        KeyCode::Char('c') => {
-            if event.modifiers == KeyModifiers::CONTROL {
-                app.quit();
-            }
+        Action::Help => {
+            app.show_help = true;
+            app.help_state = Default::default();
+        }
+        Action::ToggleGraticule => {
+            app.world_map_state.show_graticule = !app.world_map_state.show_graticule;
+        }
+        Action::ToggleHeatmap => {
+            app.world_map_state.mode = match app.world_map_state.mode {
+                MapMode::Markers => MapMode::Heatmap,
+                MapMode::Heatmap => MapMode::Markers,
+            };
+        }
+        Action::NextAltitudeBand => {
+            app.world_map_state.altitude_band = app.world_map_state.altitude_band.next();
+        }
+        Action::ToggleFollow => {
+            app.world_map_state.follow = !app.world_map_state.follow;
+        }
+        Action::ZoomIn => {
+            app.world_map_state.viewport.zoom_in();
         }
-        _ => {}
+        Action::ZoomOut => {
+            app.world_map_state.viewport.zoom_out();
+        }
+        Action::ResetViewport => {
+            app.world_map_state.viewport = Viewport::default();
+            app.world_map_state.follow = false;
+        }
+        Action::ToggleMeasurement => {
+            app.world_map_state.measurement = match app.world_map_state.measurement {
+                Some(_) => None,
+                None => Some(Measurement::default()),
+            };
+        }
+        Action::SwitchView => {
+            app.view = match app.view {
+                View::WorldMap => View::GeoBelt,
+                View::GeoBelt => View::WorldMap,
+            };
+        }
+        Action::CycleColorRule => {
+            app.legend_state
+                .cycle_rule(&app.satellites_state.objects, app.satellites_state.time);
+        }
+        Action::CopyTle => app.copy_selected_object(ExportFormat::Tle),
+        Action::CopyOmmJson => app.copy_selected_object(ExportFormat::OmmJson),
+        Action::CopyOmmXml => app.copy_selected_object(ExportFormat::OmmXml),
+        Action::CopyStateVector => app.copy_selected_object(ExportFormat::StateVector),
+        Action::FocusNext => {
+            app.focus = app.focus.next();
+        }
+        Action::FocusPrevious => {
+            app.focus = app.focus.previous();
+        }
+        // Pass other actions to the focused panel
+        _ => match (app.focus, app.view) {
+            (Focus::Main, View::WorldMap) => world_map::handle_action(action, app).await?,
+            (Focus::Main, View::GeoBelt) => geo_belt::handle_action(action, app).await?,
+            (Focus::ObjectInformation, _) => object_information::handle_action(action, app).await?,
+            (Focus::Satellites, _) => satellites::handle_action(action, app).await?,
+        },
     }
     Ok(())
 }
 
 async fn handle_mouse_events(event: MouseEvent, app: &mut App) -> Result<()> {
-    world_map::handle_mouse_events(event, app).await?;
+    match app.view {
+        View::WorldMap => world_map::handle_mouse_events(event, app).await?,
+        View::GeoBelt => geo_belt::handle_mouse_events(event, app).await?,
+    }
     object_information::handle_mouse_events(event, app).await?;
+    legend::handle_mouse_events(event, app).await?;
     satellites::handle_mouse_events(event, app).await?;
     Ok(())
 }
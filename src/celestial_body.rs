@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use strum::{Display, EnumIter};
+
+use crate::object::{gmst_from_julian_days, julian_days_from_utc};
+
+/// A body of the solar system visible from the earth.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Display, EnumIter)]
+pub enum CelestialBody {
+    Sun,
+    Moon,
+}
+
+impl CelestialBody {
+    /// Returns the symbol of the body.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Sun => "☀",
+            Self::Moon => "☾",
+        }
+    }
+
+    /// Returns the position of the body in the ECEF frame in km.
+    ///
+    /// Uses the low-precision formulae of the Astronomical Almanac, accurate to about 0.01° for
+    /// the Sun and 0.3° for the Moon.
+    pub fn position(&self, time: DateTime<Utc>) -> [f64; 3] {
+        const J2000_EPOCH: f64 = 2451545.0; // Julian Date for J2000.0 epoch
+
+        let julian_days = julian_days_from_utc(time);
+        let days = julian_days - J2000_EPOCH;
+        let (right_ascension, declination, distance) = match self {
+            Self::Sun => sun_equatorial(days),
+            Self::Moon => moon_equatorial(days),
+        };
+
+        let hour_angle = right_ascension - gmst_from_julian_days(julian_days);
+        let (sin_dec, cos_dec) = declination.sin_cos();
+        [
+            distance * cos_dec * hour_angle.cos(),
+            distance * cos_dec * hour_angle.sin(),
+            distance * sin_dec,
+        ]
+    }
+
+    /// Returns the point on the surface directly below the body, as (longitude, latitude) in deg.
+    pub fn sub_point(&self, time: DateTime<Utc>) -> (f64, f64) {
+        let [x, y, z] = self.position(time);
+        let longitude = y.atan2(x).to_degrees();
+        let latitude = z.atan2(x.hypot(y)).to_degrees();
+        (longitude, latitude)
+    }
+}
+
+/// Returns the right ascension and declination in radians and the distance in km of the Sun.
+///
+/// # Arguments
+/// * `days` - Days since the J2000.0 epoch
+fn sun_equatorial(days: f64) -> (f64, f64, f64) {
+    const ASTRONOMICAL_UNIT: f64 = 149_597_870.7; // km
+
+    let mean_longitude = 280.460 + 0.9856474 * days;
+    let mean_anomaly = (357.528 + 0.9856003 * days).to_radians();
+    let longitude =
+        (mean_longitude + 1.915 * mean_anomaly.sin() + 0.020 * (2.0 * mean_anomaly).sin())
+            .to_radians();
+    let distance = 1.00014 - 0.01671 * mean_anomaly.cos() - 0.00014 * (2.0 * mean_anomaly).cos();
+
+    let (right_ascension, declination) = ecliptic_to_equatorial(days, longitude, 0.0);
+    (right_ascension, declination, distance * ASTRONOMICAL_UNIT)
+}
+
+/// Returns the right ascension and declination in radians and the distance in km of the Moon.
+///
+/// # Arguments
+/// * `days` - Days since the J2000.0 epoch
+fn moon_equatorial(days: f64) -> (f64, f64, f64) {
+    const EARTH_RADIUS: f64 = 6378.14; // km
+
+    let t = days / 36525.0; // Julian centuries
+    let sin = |a: f64, b: f64| (a + b * t).to_radians().sin();
+    let cos = |a: f64, b: f64| (a + b * t).to_radians().cos();
+
+    let longitude = 218.32 + 481267.881 * t + 6.29 * sin(135.0, 477198.87)
+        - 1.27 * sin(259.3, -413335.36)
+        + 0.66 * sin(235.7, 890534.22)
+        + 0.21 * sin(269.9, 954397.74)
+        - 0.19 * sin(357.5, 35999.05)
+        - 0.11 * sin(186.5, 966404.03);
+    let latitude = 5.13 * sin(93.3, 483202.02) + 0.28 * sin(228.2, 960400.89)
+        - 0.28 * sin(318.3, 6003.15)
+        - 0.17 * sin(217.6, -407332.21);
+    let parallax = 0.9508
+        + 0.0518 * cos(135.0, 477198.87)
+        + 0.0095 * cos(259.3, -413335.36)
+        + 0.0078 * cos(235.7, 890534.22)
+        + 0.0028 * cos(269.9, 954397.74);
+
+    let (right_ascension, declination) =
+        ecliptic_to_equatorial(days, longitude.to_radians(), latitude.to_radians());
+    let distance = EARTH_RADIUS / parallax.to_radians().sin();
+    (right_ascension, declination, distance)
+}
+
+/// Converts ecliptic longitude and latitude to right ascension and declination, all in radians.
+fn ecliptic_to_equatorial(days: f64, longitude: f64, latitude: f64) -> (f64, f64) {
+    let obliquity = (23.439 - 0.0000004 * days).to_radians();
+    let (sin_eps, cos_eps) = obliquity.sin_cos();
+    let (sin_lon, cos_lon) = longitude.sin_cos();
+    let (sin_lat, cos_lat) = latitude.sin_cos();
+
+    let x = cos_lat * cos_lon;
+    let y = cos_eps * cos_lat * sin_lon - sin_eps * sin_lat;
+    let z = sin_eps * cos_lat * sin_lon + cos_eps * sin_lat;
+    (y.atan2(x), z.asin())
+}
@@ -0,0 +1,44 @@
+use std::io::Write;
+
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use strum::Display;
+
+/// Where copied text was placed.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Display)]
+pub enum ClipboardBackend {
+    /// The clipboard of the operating system.
+    #[strum(to_string = "clipboard")]
+    System,
+    /// The clipboard of the terminal, set through an OSC 52 escape sequence.
+    ///
+    /// Works over SSH and without a display, if the terminal supports it.
+    #[strum(to_string = "terminal clipboard")]
+    Terminal,
+}
+
+/// Copies the text to the system clipboard, or to the terminal clipboard if there is no system
+/// clipboard available.
+pub fn copy(text: &str) -> Result<ClipboardBackend> {
+    let copied = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text));
+    if copied.is_ok() {
+        return Ok(ClipboardBackend::System);
+    }
+    copy_osc52(text)?;
+    Ok(ClipboardBackend::Terminal)
+}
+
+/// Copies the text to the terminal clipboard with an OSC 52 escape sequence.
+///
+/// The terminal gives no feedback, so success only means the sequence was written.
+fn copy_osc52(text: &str) -> Result<()> {
+    let mut sequence = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+    // tmux only forwards escape sequences to the outer terminal in a passthrough sequence
+    if std::env::var_os("TMUX").is_some() {
+        sequence = format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"));
+    }
+    let mut stdout = std::io::stdout();
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::{
+    ground_station::GroundStation,
+    keymap::{Action, KeyList, Keymap},
+    style_rule::StyleRule,
+    units::Units,
+    widgets::object_information::InformationField,
+};
+
+/// User configuration.
+///
+/// Read from `tracker/config.toml` in the user's configuration directory.
+#[derive(Clone, Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Ground stations and other points of interest.
+    pub ground_stations: Vec<GroundStation>,
+    /// Name of the ground station from which the Sun, Moon and passes of objects are observed.
+    ///
+    /// Defaults to the first ground station.
+    pub observer: Option<String>,
+    /// NORAD IDs of the objects to keep an eye on.
+    pub watch_list: Vec<u64>,
+    /// The rule by which objects are colour coded.
+    pub color_by: StyleRule,
+    /// Whether to draw the world map as an image if the terminal supports Sixel, Kitty or iTerm2
+    /// graphics.
+    pub graphics: bool,
+    /// Units and formats in which values are displayed.
+    pub units: Units,
+    /// Fields shown in the object information panel, in order.
+    ///
+    /// Defaults to [`InformationField::DEFAULT`].
+    pub information_fields: Option<Vec<InformationField>>,
+    /// Keys bound to actions, replacing the default keys of the actions given.
+    pub keybindings: HashMap<Action, KeyList>,
+}
+
+impl Config {
+    /// Loads the configuration.
+    ///
+    /// If the configuration file doesn't exist, returns the default configuration.
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        if !std::fs::exists(&path)? {
+            return Ok(Self::default());
+        }
+
+        let toml = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let config: Self =
+            toml::from_str(&toml).with_context(|| format!("failed to parse {}", path.display()))?;
+        // Report invalid or conflicting key bindings before starting
+        config
+            .keymap()
+            .with_context(|| format!("invalid key bindings in {}", path.display()))?;
+        Ok(config)
+    }
+
+    /// Returns the ground station from which the Sun, Moon and passes of objects are observed.
+    pub fn observer(&self) -> Option<&GroundStation> {
+        match &self.observer {
+            Some(name) => self
+                .ground_stations
+                .iter()
+                .find(|station| &station.name == name),
+            None => self.ground_stations.first(),
+        }
+    }
+
+    /// Returns the fields shown in the object information panel, in order.
+    pub fn information_fields(&self) -> &[InformationField] {
+        self.information_fields
+            .as_deref()
+            .unwrap_or(InformationField::DEFAULT)
+    }
+
+    /// Returns the keys bound to each action.
+    pub fn keymap(&self) -> Result<Keymap> {
+        Keymap::new(&self.keybindings)
+    }
+
+    /// Returns the path of the configuration file.
+    pub fn path() -> PathBuf {
+        dirs::config_dir()
+            .expect("failed to get config directory")
+            .join("tracker/config.toml")
+    }
+}
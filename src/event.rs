@@ -22,20 +22,32 @@ pub struct EventHandler {
     sender: mpsc::UnboundedSender<Event>,
     /// Event receiver channel.
     receiver: mpsc::UnboundedReceiver<Event>,
-    /// Event handler thread.
-    handler: tokio::task::JoinHandle<()>,
+    /// Event handler thread, spawned by [`EventHandler::start`].
+    handler: Option<tokio::task::JoinHandle<()>>,
 }
 
 impl EventHandler {
     /// Constructs a new instance of [`EventHandler`].
     pub fn new() -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Self {
+            sender,
+            receiver,
+            handler: None,
+        }
+    }
+
+    /// Spawns the event handler thread.
+    ///
+    /// Terminal events are read from stdin from then on, so queries to the terminal must be
+    /// made before calling this.
+    pub fn start(&mut self) {
         const UPDATE_RATE: f64 = 10.0;
         const RENDER_RATE: f64 = 60.0;
 
         let update_period = Duration::from_secs_f64(1.0 / UPDATE_RATE);
         let render_period = Duration::from_secs_f64(1.0 / RENDER_RATE);
-        let (sender, receiver) = mpsc::unbounded_channel();
-        let _sender = sender.clone();
+        let _sender = self.sender.clone();
         let handler = tokio::spawn(async move {
             let mut reader = crossterm::event::EventStream::new();
             let mut update_interval = tokio::time::interval(update_period);
@@ -54,10 +66,8 @@ impl EventHandler {
                   }
                   Some(Ok(event)) = crossterm_event => {
                     match event {
-                      CrosstermEvent::Key(key) => {
-                        if key.kind == crossterm::event::KeyEventKind::Press {
-                          _sender.send(Event::Key(key)).unwrap();
-                        }
+                      CrosstermEvent::Key(key) if key.kind == crossterm::event::KeyEventKind::Press => {
+                        _sender.send(Event::Key(key)).unwrap();
                       },
                       CrosstermEvent::Mouse(mouse) => {
                         _sender.send(Event::Mouse(mouse)).unwrap();
@@ -68,11 +78,7 @@ impl EventHandler {
                 };
             }
         });
-        Self {
-            sender,
-            receiver,
-            handler,
-        }
+        self.handler = Some(handler);
     }
 
     /// Receive the next event from the handler thread.
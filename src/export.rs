@@ -0,0 +1,376 @@
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use strum::{Display, EnumIter};
+use ureq::serde_json;
+
+use crate::object::Object;
+
+/// Format in which an object can be exported for use in other tools.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Display, EnumIter)]
+pub enum ExportFormat {
+    /// Two-line element set, preceded by the name of the object.
+    #[strum(to_string = "TLE")]
+    Tle,
+    /// CCSDS Orbit Mean-Elements Message in JSON, as served by CelesTrak.
+    #[strum(to_string = "OMM JSON")]
+    OmmJson,
+    /// CCSDS Orbit Mean-Elements Message in XML.
+    #[strum(to_string = "OMM XML")]
+    OmmXml,
+    /// CCSDS Orbit Parameter Message with the TEME position and velocity at the given time.
+    #[strum(to_string = "state vector")]
+    StateVector,
+}
+
+impl ExportFormat {
+    /// Exports the object, propagated to `time` for formats holding a state.
+    pub fn export(self, object: &Object, time: DateTime<Utc>) -> Result<String, sgp4::Error> {
+        Ok(match self {
+            Self::Tle => tle(object).join("\n"),
+            Self::OmmJson => omm_json(object),
+            Self::OmmXml => omm_xml(object),
+            Self::StateVector => opm(object, time)?,
+        })
+    }
+}
+
+/// Returns the title line and the two lines of the TLE of the object.
+pub fn tle(object: &Object) -> [String; 3] {
+    let norad_id = alpha5(object.norad_id());
+    let epoch = object.epoch();
+    let day_of_year = epoch.ordinal() as f64
+        + (epoch.num_seconds_from_midnight() as f64 + epoch.nanosecond() as f64 / 1e9) / 86400.0;
+    // `1998-067A` becomes `98067A`
+    let designator = match object.cospar_id().split_once('-') {
+        Some((year, number)) => format!("{}{}", &year[year.len().saturating_sub(2)..], number),
+        None if object.cospar_id() != "Unknown" => object.cospar_id().clone(),
+        None => String::new(),
+    };
+
+    let line1 = format!(
+        "1 {}{} {:8} {:02}{:012.8} {} {} {} {} {:4}",
+        norad_id,
+        object.classification(),
+        designator,
+        epoch.year() % 100,
+        day_of_year,
+        first_derivative(object.mean_motion_dot()),
+        exponential(object.mean_motion_ddot()),
+        exponential(object.drag_term()),
+        object.ephemeris_type(),
+        object.element_set_number() % 10000,
+    );
+    let line2 = format!(
+        "2 {} {:8.4} {:8.4} {:07.0} {:8.4} {:8.4} {:11.8}{:5}",
+        norad_id,
+        object.inclination(),
+        object.right_ascension(),
+        object.eccentricity() * 1e7,
+        object.argument_of_perigee(),
+        object.mean_anomaly(),
+        object.mean_motion(),
+        object.revolution_number() % 100000,
+    );
+    [
+        object.name().clone(),
+        with_checksum(line1),
+        with_checksum(line2),
+    ]
+}
+
+fn omm_json(object: &Object) -> String {
+    let omm = serde_json::json!({
+        "OBJECT_NAME": object.name(),
+        "OBJECT_ID": object.cospar_id(),
+        "EPOCH": omm_time(object.epoch()),
+        "MEAN_MOTION": object.mean_motion(),
+        "ECCENTRICITY": object.eccentricity(),
+        "INCLINATION": object.inclination(),
+        "RA_OF_ASC_NODE": object.right_ascension(),
+        "ARG_OF_PERICENTER": object.argument_of_perigee(),
+        "MEAN_ANOMALY": object.mean_anomaly(),
+        "EPHEMERIS_TYPE": object.ephemeris_type(),
+        "CLASSIFICATION_TYPE": object.classification().to_string(),
+        "NORAD_CAT_ID": object.norad_id(),
+        "ELEMENT_SET_NO": object.element_set_number(),
+        "REV_AT_EPOCH": object.revolution_number(),
+        "BSTAR": object.drag_term(),
+        "MEAN_MOTION_DOT": object.mean_motion_dot(),
+        "MEAN_MOTION_DDOT": object.mean_motion_ddot(),
+    });
+    serde_json::to_string_pretty(&omm).unwrap()
+}
+
+fn omm_xml(object: &Object) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<ndm xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:noNamespaceSchemaLocation="https://sanaregistry.org/r/ndmxml_unqualified/ndmxml-2.0.0-master-2.0.xsd">
+  <omm id="CCSDS_OMM_VERS" version="2.0">
+    <header>
+      <CREATION_DATE>{}</CREATION_DATE>
+      <ORIGINATOR>{}</ORIGINATOR>
+    </header>
+    <body>
+      <segment>
+        <metadata>
+          <OBJECT_NAME>{}</OBJECT_NAME>
+          <OBJECT_ID>{}</OBJECT_ID>
+          <CENTER_NAME>EARTH</CENTER_NAME>
+          <REF_FRAME>TEME</REF_FRAME>
+          <TIME_SYSTEM>UTC</TIME_SYSTEM>
+          <MEAN_ELEMENT_THEORY>SGP4</MEAN_ELEMENT_THEORY>
+        </metadata>
+        <data>
+          <meanElements>
+            <EPOCH>{}</EPOCH>
+            <MEAN_MOTION>{}</MEAN_MOTION>
+            <ECCENTRICITY>{}</ECCENTRICITY>
+            <INCLINATION>{}</INCLINATION>
+            <RA_OF_ASC_NODE>{}</RA_OF_ASC_NODE>
+            <ARG_OF_PERICENTER>{}</ARG_OF_PERICENTER>
+            <MEAN_ANOMALY>{}</MEAN_ANOMALY>
+          </meanElements>
+          <tleParameters>
+            <EPHEMERIS_TYPE>{}</EPHEMERIS_TYPE>
+            <CLASSIFICATION_TYPE>{}</CLASSIFICATION_TYPE>
+            <NORAD_CAT_ID>{}</NORAD_CAT_ID>
+            <ELEMENT_SET_NO>{}</ELEMENT_SET_NO>
+            <REV_AT_EPOCH>{}</REV_AT_EPOCH>
+            <BSTAR>{}</BSTAR>
+            <MEAN_MOTION_DOT>{}</MEAN_MOTION_DOT>
+            <MEAN_MOTION_DDOT>{}</MEAN_MOTION_DDOT>
+          </tleParameters>
+        </data>
+      </segment>
+    </body>
+  </omm>
+</ndm>"#,
+        omm_time(Utc::now()),
+        ORIGINATOR,
+        escape_xml(object.name()),
+        escape_xml(object.cospar_id()),
+        omm_time(object.epoch()),
+        object.mean_motion(),
+        object.eccentricity(),
+        object.inclination(),
+        object.right_ascension(),
+        object.argument_of_perigee(),
+        object.mean_anomaly(),
+        object.ephemeris_type(),
+        object.classification(),
+        object.norad_id(),
+        object.element_set_number(),
+        object.revolution_number(),
+        object.drag_term(),
+        object.mean_motion_dot(),
+        object.mean_motion_ddot(),
+    )
+}
+
+/// Returns the CCSDS Orbit Parameter Message, in the key-value notation, of the state of the
+/// object at the given time.
+fn opm(object: &Object, time: DateTime<Utc>) -> Result<String, sgp4::Error> {
+    let prediction = object.predict_teme(time)?;
+    let [x, y, z] = prediction.position;
+    let [x_dot, y_dot, z_dot] = prediction.velocity;
+    Ok(format!(
+        "CCSDS_OPM_VERS = 2.0
+CREATION_DATE = {}
+ORIGINATOR = {}
+OBJECT_NAME = {}
+OBJECT_ID = {}
+CENTER_NAME = EARTH
+REF_FRAME = TEME
+TIME_SYSTEM = UTC
+EPOCH = {}
+X = {:.6} [km]
+Y = {:.6} [km]
+Z = {:.6} [km]
+X_DOT = {:.9} [km/s]
+Y_DOT = {:.9} [km/s]
+Z_DOT = {:.9} [km/s]",
+        omm_time(Utc::now()),
+        ORIGINATOR,
+        object.name(),
+        object.cospar_id(),
+        omm_time(time),
+        x,
+        y,
+        z,
+        x_dot,
+        y_dot,
+        z_dot,
+    ))
+}
+
+const ORIGINATOR: &str = "tracker";
+
+fn omm_time(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%S%.6f").to_string()
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Formats a catalogue number in 5 columns, using the Alpha-5 scheme above 99999.
+fn alpha5(norad_id: u64) -> String {
+    // Letters I and O are skipped as they resemble 1 and 0
+    const LETTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+    match norad_id {
+        0..=99999 => format!("{:05}", norad_id),
+        _ => {
+            let letter = LETTERS
+                .get((norad_id / 10000 - 10) as usize)
+                .map_or('?', |&letter| letter as char);
+            format!("{}{:04}", letter, norad_id % 10000)
+        }
+    }
+}
+
+/// Formats the first derivative of the mean motion, e.g. `-.00002182`.
+fn first_derivative(value: f64) -> String {
+    let sign = if value < 0.0 { '-' } else { ' ' };
+    let digits = format!("{:.8}", value.abs().min(0.99999999));
+    format!("{}{}", sign, digits.trim_start_matches('0'))
+}
+
+/// Formats a value in the exponential notation with an assumed leading decimal point, e.g.
+/// `-11606-4` for -0.11606e-4.
+fn exponential(value: f64) -> String {
+    let sign = if value < 0.0 { '-' } else { ' ' };
+    if value == 0.0 {
+        return format!("{}00000+0", sign);
+    }
+    let mut exponent = value.abs().log10().floor() as i32 + 1;
+    let mut mantissa = (value.abs() / 10_f64.powi(exponent) * 1e5).round() as u32;
+    if mantissa >= 100000 {
+        mantissa /= 10;
+        exponent += 1;
+    }
+    let exponent_sign = if exponent < 0 { '-' } else { '+' };
+    format!(
+        "{}{:05}{}{}",
+        sign,
+        mantissa,
+        exponent_sign,
+        exponent.abs().min(9)
+    )
+}
+
+/// Appends the modulo 10 checksum of a TLE line, counting minus signs as 1.
+fn with_checksum(line: String) -> String {
+    let checksum: u32 = line
+        .chars()
+        .map(|c| match c {
+            '-' => 1,
+            _ => c.to_digit(10).unwrap_or(0),
+        })
+        .sum();
+    format!("{}{}", line, checksum % 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::satellite::Satellite;
+
+    const NAME: &str = "ISS (ZARYA)";
+    const LINE1: &str = "1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927";
+    const LINE2: &str = "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537";
+
+    fn elements() -> sgp4::Elements {
+        sgp4::Elements::from_tle(Some(NAME.to_string()), LINE1.as_bytes(), LINE2.as_bytes())
+            .unwrap()
+    }
+
+    fn parse_tle(lines: &[String; 3]) -> sgp4::Elements {
+        sgp4::Elements::from_tle(
+            Some(lines[0].clone()),
+            lines[1].as_bytes(),
+            lines[2].as_bytes(),
+        )
+        .unwrap()
+    }
+
+    fn assert_same_elements(actual: &sgp4::Elements, expected: &sgp4::Elements) {
+        assert_eq!(actual.object_name, expected.object_name);
+        assert_eq!(
+            actual.international_designator,
+            expected.international_designator
+        );
+        assert_eq!(actual.norad_id, expected.norad_id);
+        // OMM epochs are written in microseconds
+        assert_eq!(
+            (actual.datetime - expected.datetime).num_microseconds(),
+            Some(0)
+        );
+        assert_eq!(actual.mean_motion_dot, expected.mean_motion_dot);
+        assert_eq!(actual.mean_motion_ddot, expected.mean_motion_ddot);
+        assert_eq!(actual.drag_term, expected.drag_term);
+        assert_eq!(actual.element_set_number, expected.element_set_number);
+        assert_eq!(actual.inclination, expected.inclination);
+        assert_eq!(actual.right_ascension, expected.right_ascension);
+        assert_eq!(actual.eccentricity, expected.eccentricity);
+        assert_eq!(actual.argument_of_perigee, expected.argument_of_perigee);
+        assert_eq!(actual.mean_anomaly, expected.mean_anomaly);
+        assert_eq!(actual.mean_motion, expected.mean_motion);
+        assert_eq!(actual.revolution_number, expected.revolution_number);
+        assert_eq!(actual.ephemeris_type, expected.ephemeris_type);
+    }
+
+    #[test]
+    fn tle_round_trip() {
+        let object = Object::from_elements(elements(), Satellite::Iss, None);
+        let lines = tle(&object);
+
+        // A zero second derivative is written with a positive exponent, which changes the
+        // checksum of the first line
+        assert_eq!(lines[0], NAME);
+        assert_eq!(
+            lines[1],
+            "1 25544U 98067A   08264.51782528 -.00002182  00000+0 -11606-4 0  2926"
+        );
+        assert_eq!(lines[2], LINE2);
+        assert_same_elements(&parse_tle(&lines), &elements());
+    }
+
+    #[test]
+    fn tle_alpha5() {
+        let mut elements = elements();
+        elements.norad_id = 123456;
+        let object = Object::from_elements(elements, Satellite::Iss, None);
+        let lines = tle(&object);
+
+        assert_eq!(
+            lines[1],
+            "1 C3456U 98067A   08264.51782528 -.00002182  00000+0 -11606-4 0  2924"
+        );
+        assert_eq!(
+            lines[2],
+            "2 C3456  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563535"
+        );
+    }
+
+    #[test]
+    fn omm_json_round_trip() {
+        let object = Object::from_elements(elements(), Satellite::Iss, None);
+        let parsed: sgp4::Elements = serde_json::from_str(&omm_json(&object)).unwrap();
+        assert_same_elements(&parsed, &elements());
+    }
+
+    #[test]
+    fn tle_fields() {
+        assert_eq!(alpha5(5), "00005");
+        assert_eq!(alpha5(99999), "99999");
+        assert_eq!(alpha5(100000), "A0000");
+        assert_eq!(alpha5(339999), "Z9999");
+        assert_eq!(first_derivative(-0.00002182), "-.00002182");
+        assert_eq!(exponential(-0.11606e-4), "-11606-4");
+        assert_eq!(exponential(0.0), " 00000+0");
+        assert_eq!(exponential(0.999999e-3), " 10000-2");
+        assert_eq!(with_checksum(LINE1[..68].to_string()), LINE1);
+        assert_eq!(with_checksum(LINE2[..68].to_string()), LINE2);
+    }
+}
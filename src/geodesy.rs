@@ -0,0 +1,69 @@
+/// Mean Earth radius (km)
+pub const EARTH_MEAN_RADIUS: f64 = 6371.0;
+
+/// Returns the central angle between two points in radians.
+///
+/// # Arguments
+/// * `from`, `to` - Longitude and latitude in deg
+pub fn central_angle(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lon1, lat1) = (from.0.to_radians(), from.1.to_radians());
+    let (lon2, lat2) = (to.0.to_radians(), to.1.to_radians());
+
+    // Haversine formula
+    let a = ((lat2 - lat1) / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+    2.0 * a.sqrt().atan2((1.0 - a).sqrt())
+}
+
+/// Returns the great-circle distance between two points on the surface in km.
+///
+/// # Arguments
+/// * `from`, `to` - Longitude and latitude in deg
+pub fn great_circle_distance(from: (f64, f64), to: (f64, f64)) -> f64 {
+    central_angle(from, to) * EARTH_MEAN_RADIUS
+}
+
+/// Returns the initial bearing of the great circle from one point to another in deg, measured
+/// clockwise from north.
+///
+/// # Arguments
+/// * `from`, `to` - Longitude and latitude in deg
+pub fn initial_bearing(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lon1, lat1) = (from.0.to_radians(), from.1.to_radians());
+    let (lon2, lat2) = (to.0.to_radians(), to.1.to_radians());
+
+    let y = (lon2 - lon1).sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * (lon2 - lon1).cos();
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+/// Returns points along the great circle between two points, including both ends.
+///
+/// The path between coincident or antipodal points is undefined, only the ends are returned.
+///
+/// # Arguments
+/// * `from`, `to` - Longitude and latitude in deg
+/// * `segments` - Number of segments between the returned points
+pub fn great_circle_path(from: (f64, f64), to: (f64, f64), segments: usize) -> Vec<(f64, f64)> {
+    let angle = central_angle(from, to);
+    if angle.sin().abs() < 1e-9 || segments == 0 {
+        return vec![from, to];
+    }
+
+    let to_vector = |(lon, lat): (f64, f64)| {
+        let (lon, lat) = (lon.to_radians(), lat.to_radians());
+        [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+    };
+    let (a, b) = (to_vector(from), to_vector(to));
+
+    // Spherical linear interpolation
+    (0..=segments)
+        .map(|i| {
+            let t = i as f64 / segments as f64;
+            let wa = ((1.0 - t) * angle).sin() / angle.sin();
+            let wb = (t * angle).sin() / angle.sin();
+            let [x, y, z] = [0, 1, 2].map(|i| wa * a[i] + wb * b[i]);
+            (y.atan2(x).to_degrees(), z.atan2(x.hypot(y)).to_degrees())
+        })
+        .collect()
+}
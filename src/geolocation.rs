@@ -0,0 +1,150 @@
+use reverse_geocoder::ReverseGeocoder;
+use serde::Deserialize;
+
+use crate::{
+    geodesy::{great_circle_distance, initial_bearing},
+    units::Units,
+};
+
+/// Describes where on the Earth's surface a point lies, by the sea it is over or its position
+/// relative to the nearest populated place.
+pub struct Geolocator {
+    geocoder: ReverseGeocoder,
+    regions: Vec<Region>,
+}
+
+/// A land mass or named body of water.
+#[derive(Deserialize)]
+struct Region {
+    name: String,
+    kind: RegionKind,
+    /// Outline as longitude and latitude pairs in deg.
+    polygon: Vec<[f64; 2]>,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RegionKind {
+    Land,
+    Sea,
+}
+
+#[derive(Deserialize)]
+struct Regions {
+    region: Vec<Region>,
+}
+
+/// Where a point lies.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Location<'a> {
+    /// Over open water, away from any populated place.
+    Sea(&'a str),
+    /// Near a populated place.
+    Place {
+        name: &'a str,
+        /// Country name, or code if the name is unknown.
+        country: &'a str,
+        /// Distance from the place in km.
+        distance: f64,
+        /// Bearing from the place in deg.
+        bearing: f64,
+    },
+}
+
+impl Geolocator {
+    /// Distance from the nearest place within which a point over water is described relative to
+    /// the place, in km.
+    const COASTAL_DISTANCE: f64 = 100.0;
+
+    /// Constructs a new instance of [`Geolocator`], loading the embedded datasets.
+    pub fn new() -> Self {
+        let regions: Regions = toml::from_str(include_str!("../assets/regions.toml"))
+            .expect("embedded regions should be valid");
+        Self {
+            geocoder: ReverseGeocoder::new(),
+            regions: regions.region,
+        }
+    }
+
+    /// Returns where the point at the given longitude and latitude lies.
+    pub fn locate(&self, lon: f64, lat: f64) -> Location<'_> {
+        let record = self.geocoder.search((lat, lon)).record;
+        let distance = great_circle_distance((record.lon, record.lat), (lon, lat));
+
+        if distance > Self::COASTAL_DISTANCE {
+            if let Some(region) = self.region(lon, lat) {
+                if region.kind == RegionKind::Sea {
+                    return Location::Sea(&region.name);
+                }
+            }
+        }
+
+        let country = isocountry::CountryCode::for_alpha2(&record.cc)
+            .map(|country| country.name())
+            .unwrap_or(&record.cc);
+        Location::Place {
+            name: &record.name,
+            country,
+            distance,
+            bearing: initial_bearing((record.lon, record.lat), (lon, lat)),
+        }
+    }
+
+    /// Returns the first region containing the point.
+    fn region(&self, lon: f64, lat: f64) -> Option<&Region> {
+        self.regions.iter().find(|region| {
+            // Polygons crossing the date line extend past ±180°
+            [lon, lon + 360.0, lon - 360.0]
+                .into_iter()
+                .any(|lon| contains(&region.polygon, lon, lat))
+        })
+    }
+}
+
+impl Default for Geolocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Location<'_> {
+    /// Formats the location, e.g. `12 km NE of Oslo, Norway` or `Pacific Ocean`.
+    pub fn describe(&self, units: &Units) -> String {
+        /// Distance below which a point is considered to be at the place, in km.
+        const NEARBY_DISTANCE: f64 = 5.0;
+        const COMPASS_POINTS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+
+        match self {
+            Self::Sea(name) => name.to_string(),
+            Self::Place {
+                name,
+                country,
+                distance,
+                bearing,
+            } => {
+                let place = if country.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{}, {}", name, country)
+                };
+                if *distance < NEARBY_DISTANCE {
+                    return place;
+                }
+                let point = COMPASS_POINTS[((bearing / 45.0).round() as usize) % 8];
+                format!("{} {} of {}", units.distance(*distance, 0), point, place)
+            }
+        }
+    }
+}
+
+/// Returns whether the polygon contains the point, by casting a ray eastwards.
+fn contains(polygon: &[[f64; 2]], lon: f64, lat: f64) -> bool {
+    let mut inside = false;
+    for (i, [x1, y1]) in polygon.iter().enumerate() {
+        let [x2, y2] = polygon[(i + 1) % polygon.len()];
+        if (*y1 > lat) != (y2 > lat) && lon < x1 + (lat - y1) * (x2 - x1) / (y2 - y1) {
+            inside = !inside;
+        }
+    }
+    inside
+}
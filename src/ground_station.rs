@@ -0,0 +1,150 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+
+use crate::object::{lat_lon_alt_to_ecef, Object, State};
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct GroundStation {
+    /// The name of the ground station.
+    pub name: String,
+    /// Geodetic latitude in deg.
+    pub latitude: f64,
+    /// Geodetic longitude in deg.
+    pub longitude: f64,
+    /// Height above WGS84 ellipsoid in km.
+    #[serde(default)]
+    pub altitude: f64,
+    /// Minimum elevation for line-of-sight in deg.
+    #[serde(default)]
+    pub elevation_mask: f64,
+}
+
+/// Topocentric coordinates of an object as seen from a ground station.
+#[derive(Clone, Copy, Debug)]
+pub struct LookAngles {
+    /// Angle measured clockwise from north in deg.
+    pub azimuth: f64,
+    /// Angle above the horizon in deg.
+    pub elevation: f64,
+    /// Distance between the ground station and the object in km.
+    pub range: f64,
+}
+
+impl GroundStation {
+    /// Returns the look angles of the object in the given state.
+    pub fn look_angles(&self, state: &State) -> LookAngles {
+        self.look_angles_to(lat_lon_alt_to_ecef([
+            state.latitude(),
+            state.longitude(),
+            state.altitude(),
+        ]))
+    }
+
+    /// Returns `true` if the object in the given state is above the elevation mask.
+    pub fn has_line_of_sight(&self, state: &State) -> bool {
+        self.look_angles(state).elevation >= self.elevation_mask
+    }
+
+    /// Returns the time the object next rises above the elevation mask, searching up to `window`
+    /// ahead of `time`.
+    ///
+    /// Returns `time` if the object is already above the elevation mask, or `None` if it doesn't
+    /// rise within the window.
+    pub fn next_pass(
+        &self,
+        object: &Object,
+        time: DateTime<Utc>,
+        window: Duration,
+    ) -> Option<DateTime<Utc>> {
+        // Short enough not to step over low passes of LEO objects
+        const STEP: Duration = Duration::seconds(30);
+
+        let is_visible = |time| {
+            object
+                .predict(time)
+                .is_ok_and(|state| self.has_line_of_sight(&state))
+        };
+        if is_visible(time) {
+            return Some(time);
+        }
+
+        let mut previous = time;
+        while previous - time < window {
+            let next = previous + STEP;
+            if is_visible(next) {
+                // Bisect to about one second
+                let (mut low, mut high) = (previous, next);
+                while high - low > Duration::seconds(1) {
+                    let middle = low + (high - low) / 2;
+                    if is_visible(middle) {
+                        high = middle;
+                    } else {
+                        low = middle;
+                    }
+                }
+                return Some(high);
+            }
+            previous = next;
+        }
+        None
+    }
+
+    /// Returns the boundary of the region from which an object at the given altitude is above
+    /// the elevation mask, as (longitude, latitude) pairs in deg.
+    ///
+    /// # Arguments
+    /// * `altitude` - Height of the object above the surface in kilometers
+    pub fn visibility_circle(&self, altitude: f64) -> Vec<(f64, f64)> {
+        const EARTH_RADIUS: f64 = 6371.0; // Mean Earth radius (km)
+
+        let mask = self.elevation_mask.to_radians();
+        let central_angle = (EARTH_RADIUS / (EARTH_RADIUS + altitude) * mask.cos()).acos() - mask;
+        if central_angle <= 0.0 {
+            return Vec::new();
+        }
+
+        let lat = self.latitude.to_radians();
+        let lon = self.longitude.to_radians();
+        (0..=360)
+            .step_by(5)
+            .map(|bearing| {
+                let bearing = (bearing as f64).to_radians();
+                let point_lat = (lat.sin() * central_angle.cos()
+                    + lat.cos() * central_angle.sin() * bearing.cos())
+                .asin();
+                let point_lon = lon
+                    + (bearing.sin() * central_angle.sin() * lat.cos())
+                        .atan2(central_angle.cos() - lat.sin() * point_lat.sin());
+                let point_lon = (point_lon.to_degrees() + 540.0).rem_euclid(360.0) - 180.0;
+                (point_lon, point_lat.to_degrees())
+            })
+            .collect()
+    }
+
+    /// Returns the look angles of the given position.
+    ///
+    /// # Arguments
+    /// * `position` - A 3D position vector [x, y, z] in the ECEF frame (kilometers)
+    pub fn look_angles_to(&self, position: [f64; 3]) -> LookAngles {
+        let station = lat_lon_alt_to_ecef([self.latitude, self.longitude, self.altitude]);
+        let [dx, dy, dz] = [
+            position[0] - station[0],
+            position[1] - station[1],
+            position[2] - station[2],
+        ];
+        let range = (dx * dx + dy * dy + dz * dz).sqrt();
+
+        // Rotate the range vector into the local East-North-Up frame
+        let (sin_lat, cos_lat) = self.latitude.to_radians().sin_cos();
+        let (sin_lon, cos_lon) = self.longitude.to_radians().sin_cos();
+        let east = -sin_lon * dx + cos_lon * dy;
+        let north = -sin_lat * cos_lon * dx - sin_lat * sin_lon * dy + cos_lat * dz;
+        let up = cos_lat * cos_lon * dx + cos_lat * sin_lon * dy + sin_lat * dz;
+
+        LookAngles {
+            azimuth: east.atan2(north).to_degrees().rem_euclid(360.0),
+            elevation: (up / range).asin().to_degrees(),
+            range,
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use anyhow::{anyhow, bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use strum::{Display, EnumIter, EnumMessage, IntoEnumIterator};
+
+/// An action triggered by a key.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Display, EnumIter, EnumMessage, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Action {
+    #[strum(message = "Quit")]
+    Quit,
+    #[strum(message = "Show or hide the key bindings")]
+    Help,
+    #[strum(message = "Focus the next panel")]
+    FocusNext,
+    #[strum(message = "Focus the previous panel")]
+    FocusPrevious,
+    #[strum(message = "Move up, or pan the map north")]
+    Up,
+    #[strum(message = "Move down, or pan the map south")]
+    Down,
+    #[strum(message = "Pan the map west")]
+    Left,
+    #[strum(message = "Pan the map east")]
+    Right,
+    #[strum(message = "Move up a page")]
+    PageUp,
+    #[strum(message = "Move down a page")]
+    PageDown,
+    #[strum(message = "Move to the first item")]
+    First,
+    #[strum(message = "Move to the last item")]
+    Last,
+    #[strum(message = "Toggle the group, copy the value or select the object")]
+    Activate,
+    #[strum(message = "Select the next object on the map")]
+    NextObject,
+    #[strum(message = "Select the previous object on the map")]
+    PreviousObject,
+    #[strum(message = "Toggle the graticule")]
+    ToggleGraticule,
+    #[strum(message = "Toggle the heatmap")]
+    ToggleHeatmap,
+    #[strum(message = "Include the next altitude band in the heatmap")]
+    NextAltitudeBand,
+    #[strum(message = "Toggle following the selected object")]
+    ToggleFollow,
+    #[strum(message = "Zoom in")]
+    ZoomIn,
+    #[strum(message = "Zoom out")]
+    ZoomOut,
+    #[strum(message = "Show the whole world")]
+    ResetViewport,
+    #[strum(message = "Toggle the measurement tool")]
+    ToggleMeasurement,
+    #[strum(message = "Switch between the world map and the GEO belt")]
+    SwitchView,
+    #[strum(message = "Colour code objects by the next rule")]
+    CycleColorRule,
+    #[strum(message = "Copy the selected object as a TLE")]
+    CopyTle,
+    #[strum(message = "Copy the selected object as OMM JSON")]
+    CopyOmmJson,
+    #[strum(message = "Copy the selected object as OMM XML")]
+    CopyOmmXml,
+    #[strum(message = "Copy the state vector of the selected object")]
+    CopyStateVector,
+}
+
+impl Action {
+    /// Returns the keys bound to the action unless configured otherwise.
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Self::Quit => &["esc", "ctrl-c"],
+            Self::Help => &["?"],
+            Self::FocusNext => &["tab"],
+            Self::FocusPrevious => &["shift-tab"],
+            Self::Up => &["up", "k"],
+            Self::Down => &["down", "j"],
+            Self::Left => &["left", "h"],
+            Self::Right => &["right", "l"],
+            Self::PageUp => &["pageup"],
+            Self::PageDown => &["pagedown"],
+            Self::First => &["home"],
+            Self::Last => &["end"],
+            Self::Activate => &["enter", "space"],
+            Self::NextObject => &["n"],
+            Self::PreviousObject => &["N"],
+            Self::ToggleGraticule => &["g"],
+            Self::ToggleHeatmap => &["d"],
+            Self::NextAltitudeBand => &["a"],
+            Self::ToggleFollow => &["f"],
+            Self::ZoomIn => &["+", "="],
+            Self::ZoomOut => &["-"],
+            Self::ResetViewport => &["0"],
+            Self::ToggleMeasurement => &["m"],
+            Self::SwitchView => &["v"],
+            Self::CycleColorRule => &["c"],
+            Self::CopyTle => &["T"],
+            Self::CopyOmmJson => &["J"],
+            Self::CopyOmmXml => &["X"],
+            Self::CopyStateVector => &["S"],
+        }
+    }
+
+    /// Returns the description of the action.
+    pub fn description(self) -> &'static str {
+        self.get_message().unwrap()
+    }
+}
+
+/// Names of the keys that are not written as a single character.
+const NAMED_KEYS: [(&str, KeyCode); 15] = [
+    ("esc", KeyCode::Esc),
+    ("enter", KeyCode::Enter),
+    ("space", KeyCode::Char(' ')),
+    ("tab", KeyCode::Tab),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("insert", KeyCode::Insert),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+];
+
+/// A key with its modifiers, e.g. `ctrl-c`.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        // Shift is part of the character or of `BackTab`
+        let modifiers = match event.code {
+            KeyCode::Char(_) | KeyCode::BackTab => event.modifiers - KeyModifiers::SHIFT,
+            _ => event.modifiers,
+        };
+        Self {
+            code: event.code,
+            modifiers,
+        }
+    }
+}
+
+impl FromStr for Key {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut name = text;
+        // The key itself may be `-`
+        while let Some((modifier, rest)) = name.split_once('-').filter(|(_, rest)| !rest.is_empty())
+        {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => bail!("unknown modifier `{}` in `{}`", modifier, text),
+            };
+            name = rest;
+        }
+
+        let mut chars = name.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers -= KeyModifiers::SHIFT;
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match name.to_lowercase().as_str() {
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => {
+                    modifiers -= KeyModifiers::SHIFT;
+                    KeyCode::BackTab
+                }
+                key => match NAMED_KEYS.iter().find(|(name, _)| *name == key) {
+                    Some(&(_, code)) => code,
+                    None => match key.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                        Some(n @ 1..=12) => KeyCode::F(n),
+                        _ => bail!("unknown key `{}`", text),
+                    },
+                },
+            },
+        };
+        Ok(Self { code, modifiers })
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) || self.code == KeyCode::BackTab {
+            write!(f, "shift-")?;
+        }
+        match self.code {
+            KeyCode::Char(c) if c != ' ' => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            KeyCode::BackTab => write!(f, "tab"),
+            code => match NAMED_KEYS.iter().find(|(_, named)| *named == code) {
+                Some((name, _)) => write!(f, "{}", name),
+                None => write!(f, "{}", code),
+            },
+        }
+    }
+}
+
+/// Keys bound to an action in the configuration, either one or a list.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    fn keys(&self) -> &[String] {
+        match self {
+            Self::One(key) => std::slice::from_ref(key),
+            Self::Many(keys) => keys,
+        }
+    }
+}
+
+/// The keys bound to each action.
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    /// Keys of each action, in the order of the actions.
+    bindings: Vec<(Action, Vec<Key>)>,
+    actions: HashMap<Key, Action>,
+}
+
+impl Keymap {
+    /// Constructs a new instance of [`Keymap`], with the configured keys replacing the default
+    /// keys of their actions.
+    ///
+    /// Fails if a key is invalid or bound to more than one action.
+    pub fn new(overrides: &HashMap<Action, KeyList>) -> Result<Self> {
+        let mut bindings = Vec::new();
+        let mut actions = HashMap::new();
+        for action in Action::iter() {
+            let keys = match overrides.get(&action) {
+                Some(keys) => keys
+                    .keys()
+                    .iter()
+                    .map(|key| key.parse())
+                    .collect::<Result<Vec<Key>>>()
+                    .map_err(|err| anyhow!("invalid key for `{}`: {}", action, err))?,
+                None => action
+                    .default_keys()
+                    .iter()
+                    .map(|key| key.parse().expect("default keys should be valid"))
+                    .collect(),
+            };
+            for &key in &keys {
+                if let Some(other) = actions.insert(key, action) {
+                    if other != action {
+                        bail!("`{}` is bound to both `{}` and `{}`", key, other, action);
+                    }
+                }
+            }
+            bindings.push((action, keys));
+        }
+        Ok(Self { bindings, actions })
+    }
+
+    /// Returns the action bound to the key, if any.
+    pub fn action(&self, event: KeyEvent) -> Option<Action> {
+        self.actions.get(&Key::from(event)).copied()
+    }
+
+    /// Returns the keys bound to the action.
+    pub fn keys(&self, action: Action) -> &[Key] {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .map_or(&[], |(_, keys)| keys)
+    }
+
+    /// Returns the keys bound to each action, in the order of the actions.
+    pub fn bindings(&self) -> &[(Action, Vec<Key>)] {
+        &self.bindings
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&HashMap::new()).expect("default key bindings should not conflict")
+    }
+}
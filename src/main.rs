@@ -3,10 +3,20 @@ use anyhow::Result;
 use crate::app::App;
 
 pub mod app;
+pub mod celestial_body;
+pub mod clipboard;
+pub mod config;
 pub mod event;
+pub mod export;
+pub mod geodesy;
+pub mod geolocation;
+pub mod ground_station;
+pub mod keymap;
 pub mod object;
 pub mod satellite;
+pub mod style_rule;
 pub mod tui;
+pub mod units;
 pub mod widgets;
 
 #[tokio::main]
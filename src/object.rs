@@ -1,6 +1,10 @@
 use std::f64::consts::PI;
 
 use chrono::{DateTime, Datelike, Timelike, Utc};
+use ratatui::style::Color;
+use strum::{Display, EnumIter};
+
+use crate::satellite::{SatcatRecord, Satellite};
 
 #[derive(Clone, Debug)]
 pub struct Object {
@@ -10,9 +14,21 @@ pub struct Object {
     cospar_id: String,
     /// The NORAD ID of the object.
     norad_id: u64,
-
+    /// The group the object was fetched from.
+    group: Satellite,
+    /// The type of the object.
+    object_type: ObjectType,
+    /// The owner or country of origin of the object, as a SATCAT source code.
+    owner: Option<String>,
+
+    /// The classification of the elements, `U`, `C` or `S`.
+    classification: char,
     epoch: DateTime<Utc>,
 
+    /// Half the first time derivative of the mean motion in day⁻², as given in TLEs.
+    mean_motion_dot: f64,
+    /// A sixth of the second time derivative of the mean motion in day⁻³, as given in TLEs.
+    mean_motion_ddot: f64,
     /// Radiation pressure coefficient in earth radii⁻¹.
     drag_term: f64,
     /// Angle between the equator and the orbit plane in deg.
@@ -29,12 +45,24 @@ pub struct Object {
     mean_motion: f64,
     /// The orbit number at epoch.
     revolution_number: u64,
+    /// Running count of the element sets generated for the object.
+    element_set_number: u64,
+    /// Ephemeris type, always 0 in distributed data.
+    ephemeris_type: u8,
 
     constants: sgp4::Constants,
 }
 
 impl Object {
-    pub fn from_elements(elements: sgp4::Elements) -> Self {
+    pub fn from_elements(
+        elements: sgp4::Elements,
+        group: Satellite,
+        record: Option<&SatcatRecord>,
+    ) -> Self {
+        let object_type = record
+            .and_then(|record| record.object_type.as_deref())
+            .and_then(ObjectType::from_satcat_code)
+            .unwrap_or_else(|| ObjectType::from_name(elements.object_name.as_deref()));
         Self {
             name: elements
                 .object_name
@@ -47,7 +75,17 @@ impl Object {
                 .cloned()
                 .unwrap_or("Unknown".to_string()),
             norad_id: elements.norad_id,
+            group,
+            object_type,
+            owner: record.and_then(|record| record.owner.clone()),
+            classification: match elements.classification {
+                sgp4::Classification::Unclassified => 'U',
+                sgp4::Classification::Classified => 'C',
+                sgp4::Classification::Secret => 'S',
+            },
             epoch: DateTime::from_naive_utc_and_offset(elements.datetime, Utc),
+            mean_motion_dot: elements.mean_motion_dot,
+            mean_motion_ddot: elements.mean_motion_ddot,
             drag_term: elements.drag_term,
             inclination: elements.inclination,
             right_ascension: elements.right_ascension,
@@ -56,6 +94,8 @@ impl Object {
             mean_anomaly: elements.mean_anomaly,
             mean_motion: elements.mean_motion,
             revolution_number: elements.revolution_number,
+            element_set_number: elements.element_set_number,
+            ephemeris_type: elements.ephemeris_type,
             constants: sgp4::Constants::from_elements(&elements).unwrap(),
         }
     }
@@ -72,11 +112,39 @@ impl Object {
         self.norad_id
     }
 
+    pub fn group(&self) -> Satellite {
+        self.group
+    }
+
+    pub fn object_type(&self) -> ObjectType {
+        self.object_type
+    }
+
+    /// The owner or country of origin as a SATCAT source code, e.g. `US` or `PRC`
+    pub fn owner(&self) -> Option<&str> {
+        self.owner.as_deref()
+    }
+
+    /// The classification of the elements, `U`, `C` or `S`
+    pub fn classification(&self) -> char {
+        self.classification
+    }
+
     /// The UTC timestamp of the elements
     pub fn epoch(&self) -> DateTime<Utc> {
         self.epoch
     }
 
+    /// Half the first time derivative of the mean motion in day⁻²
+    pub fn mean_motion_dot(&self) -> f64 {
+        self.mean_motion_dot
+    }
+
+    /// A sixth of the second time derivative of the mean motion in day⁻³
+    pub fn mean_motion_ddot(&self) -> f64 {
+        self.mean_motion_ddot
+    }
+
     /// Radiation pressure coefficient in earth radii⁻¹
     pub fn drag_term(&self) -> f64 {
         self.drag_term
@@ -117,17 +185,82 @@ impl Object {
         self.revolution_number
     }
 
+    /// Running count of the element sets generated for the object
+    pub fn element_set_number(&self) -> u64 {
+        self.element_set_number
+    }
+
+    /// Ephemeris type, always 0 in distributed data
+    pub fn ephemeris_type(&self) -> u8 {
+        self.ephemeris_type
+    }
+
     pub fn orbital_period(&self) -> chrono::Duration {
         const SECONDS_PER_DAY: f64 = 24.0 * 60.0 * 60.0;
         chrono::Duration::seconds((SECONDS_PER_DAY / self.mean_motion) as i64)
     }
 
-    pub fn predict(&self, time: DateTime<Utc>) -> Result<State, sgp4::Error> {
-        let minutes_since_epoch = (time - self.epoch).num_seconds() as f64 / 60.0;
+    /// Mean semi-major axis in km
+    pub fn semi_major_axis(&self) -> f64 {
+        const MU: f64 = 398600.4418; // Earth gravitational parameter (km³/s²)
+        let mean_motion = self.mean_motion * 2.0 * PI / 86400.0; // rad/s
+        (MU / mean_motion.powi(2)).cbrt()
+    }
 
-        let prediction = self
-            .constants
-            .propagate(sgp4::MinutesSinceEpoch(minutes_since_epoch))?;
+    /// Altitude of the apogee above the equatorial radius in km
+    pub fn apogee_altitude(&self) -> f64 {
+        self.semi_major_axis() * (1.0 + self.eccentricity) - EARTH_EQUATORIAL_RADIUS
+    }
+
+    /// Altitude of the perigee above the equatorial radius in km
+    pub fn perigee_altitude(&self) -> f64 {
+        self.semi_major_axis() * (1.0 - self.eccentricity) - EARTH_EQUATORIAL_RADIUS
+    }
+
+    /// Returns the rate at which the longitude of a geosynchronous object drifts eastwards in
+    /// deg/day, derived from the mean motion.
+    pub fn drift_rate(&self) -> f64 {
+        /// Earth's rotation rate in revolutions per solar day.
+        const SIDEREAL_MEAN_MOTION: f64 = 1.00273790935;
+        (self.mean_motion - SIDEREAL_MEAN_MOTION) * 360.0
+    }
+
+    /// Returns the time elapsed from the epoch of the element set to the given time.
+    pub fn element_age(&self, time: DateTime<Utc>) -> chrono::Duration {
+        time - self.epoch
+    }
+
+    /// Returns a rough estimate of the SGP4 position error at the given time in km.
+    ///
+    /// The error grows linearly with the time from the epoch, at a rate depending on the orbit
+    /// regime; low orbits degrade fastest due to the poorly modelled atmospheric drag.
+    pub fn position_uncertainty(&self, time: DateTime<Utc>) -> f64 {
+        // Error at epoch in km and growth in km/day
+        let (initial, growth) = match self.orbit_regime() {
+            OrbitRegime::Leo => (1.0, 2.0),
+            OrbitRegime::Meo => (2.0, 1.0),
+            OrbitRegime::Geo => (5.0, 1.0),
+            OrbitRegime::Heo => (5.0, 5.0),
+        };
+        let days = self.element_age(time).num_seconds().abs() as f64 / 86400.0;
+        initial + growth * days
+    }
+
+    pub fn orbit_regime(&self) -> OrbitRegime {
+        let period = self.orbital_period().num_minutes();
+        if self.eccentricity >= 0.25 {
+            OrbitRegime::Heo
+        } else if (1400..=1460).contains(&period) {
+            OrbitRegime::Geo
+        } else if self.apogee_altitude() < 2000.0 {
+            OrbitRegime::Leo
+        } else {
+            OrbitRegime::Meo
+        }
+    }
+
+    pub fn predict(&self, time: DateTime<Utc>) -> Result<State, sgp4::Error> {
+        let prediction = self.predict_teme(time)?;
 
         let gmst = gmst_from_julian_days(julian_days_from_utc(time));
         let [lat, lon, alt] = ecef_to_lat_lon_alt(teme_to_ecef(prediction.position, gmst));
@@ -140,6 +273,120 @@ impl Object {
             velocity: prediction.velocity,
         })
     }
+
+    /// Returns the position in km and velocity in km/s in the TEME frame at the given time.
+    pub fn predict_teme(&self, time: DateTime<Utc>) -> Result<sgp4::Prediction, sgp4::Error> {
+        let minutes_since_epoch = (time - self.epoch).num_milliseconds() as f64 / 60000.0;
+        self.constants
+            .propagate(sgp4::MinutesSinceEpoch(minutes_since_epoch))
+    }
+
+    /// Returns the position and velocity in the TEME and ECEF frames at the given time.
+    pub fn predict_vectors(&self, time: DateTime<Utc>) -> Result<StateVectors, sgp4::Error> {
+        let prediction = self.predict_teme(time)?;
+        let gmst = gmst_from_julian_days(julian_days_from_utc(time));
+        let ecef_position = teme_to_ecef(prediction.position, gmst);
+        // Remove the velocity of the rotating frame, ω × r
+        let [x, y, _] = ecef_position;
+        let [vx, vy, vz] = teme_to_ecef(prediction.velocity, gmst);
+        let ecef_velocity = [
+            vx + EARTH_ROTATION_RATE * y,
+            vy - EARTH_ROTATION_RATE * x,
+            vz,
+        ];
+        Ok(StateVectors {
+            teme_position: prediction.position,
+            teme_velocity: prediction.velocity,
+            ecef_position,
+            ecef_velocity,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Display, EnumIter)]
+pub enum ObjectType {
+    Payload,
+    #[strum(to_string = "Rocket body")]
+    RocketBody,
+    Debris,
+    Unknown,
+}
+
+impl ObjectType {
+    /// Parses the object type code used by SATCAT.
+    fn from_satcat_code(code: &str) -> Option<Self> {
+        match code {
+            "PAY" => Some(Self::Payload),
+            "R/B" => Some(Self::RocketBody),
+            "DEB" => Some(Self::Debris),
+            "UNK" => Some(Self::Unknown),
+            _ => None,
+        }
+    }
+
+    /// Guesses the object type from its name, following the SATCAT naming conventions.
+    fn from_name(name: Option<&str>) -> Self {
+        match name {
+            Some(name) if name.contains("R/B") => Self::RocketBody,
+            Some(name) if name.contains(" DEB") => Self::Debris,
+            Some(_) => Self::Payload,
+            None => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Display, EnumIter)]
+pub enum OrbitRegime {
+    /// Low Earth orbit
+    #[strum(to_string = "LEO")]
+    Leo,
+    /// Medium Earth orbit
+    #[strum(to_string = "MEO")]
+    Meo,
+    /// Geosynchronous orbit
+    #[strum(to_string = "GEO")]
+    Geo,
+    /// Highly elliptical orbit
+    #[strum(to_string = "HEO")]
+    Heo,
+}
+
+/// Staleness of an element set, by the time elapsed since its epoch.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Display, EnumIter)]
+pub enum Staleness {
+    #[strum(to_string = "< 1 day")]
+    Fresh,
+    #[strum(to_string = "1-3 days")]
+    Recent,
+    #[strum(to_string = "3-14 days")]
+    Stale,
+    #[strum(to_string = "> 14 days")]
+    Expired,
+}
+
+impl Staleness {
+    /// Returns the staleness of an element set of the given age.
+    ///
+    /// Element sets with an epoch in the future are as stale as past ones of the same age.
+    pub fn from_age(age: chrono::Duration) -> Self {
+        let days = age.num_hours().abs() as f64 / 24.0;
+        match days {
+            ..1.0 => Self::Fresh,
+            1.0..3.0 => Self::Recent,
+            3.0..14.0 => Self::Stale,
+            _ => Self::Expired,
+        }
+    }
+
+    /// Returns the colour of the staleness, from green for fresh to magenta for expired.
+    pub fn color(self) -> Color {
+        match self {
+            Self::Fresh => Color::LightGreen,
+            Self::Recent => Color::LightYellow,
+            Self::Stale => Color::LightRed,
+            Self::Expired => Color::LightMagenta,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -166,8 +413,173 @@ impl State {
     }
 }
 
+/// Position and velocity vectors of an object in the inertial and Earth-fixed frames.
+#[derive(Clone, Debug)]
+pub struct StateVectors {
+    /// Position in the TEME frame in km.
+    pub teme_position: [f64; 3],
+    /// Velocity in the TEME frame in km/s.
+    pub teme_velocity: [f64; 3],
+    /// Position in the ECEF frame in km.
+    pub ecef_position: [f64; 3],
+    /// Velocity relative to the rotating Earth in the ECEF frame in km/s.
+    pub ecef_velocity: [f64; 3],
+}
+
+impl StateVectors {
+    /// Returns the speed relative to the rotating Earth in km/s.
+    pub fn earth_fixed_speed(&self) -> f64 {
+        norm(self.ecef_velocity)
+    }
+
+    /// Returns the motion of the object relative to the ground below it.
+    pub fn ground_motion(&self) -> GroundMotion {
+        let [lat, lon, alt] = ecef_to_lat_lon_alt(self.ecef_position);
+        let (sin_lat, cos_lat) = lat.to_radians().sin_cos();
+        let (sin_lon, cos_lon) = lon.to_radians().sin_cos();
+        let [vx, vy, vz] = self.ecef_velocity;
+
+        // Components of the Earth-fixed velocity in the local east, north and up directions
+        let east = -sin_lon * vx + cos_lon * vy;
+        let north = -sin_lat * cos_lon * vx - sin_lat * sin_lon * vy + cos_lat * vz;
+        let up = cos_lat * cos_lon * vx + cos_lat * sin_lon * vy + sin_lat * vz;
+
+        // The sub-satellite point moves slower than the object by the ratio of their radii
+        let radius = norm(self.ecef_position);
+        let ground_radius = radius - alt;
+        GroundMotion {
+            ground_speed: east.hypot(north) * ground_radius / radius,
+            heading: east.atan2(north).to_degrees().rem_euclid(360.0),
+            altitude_rate: up,
+        }
+    }
+}
+
+/// Motion of an object relative to the surface of the Earth.
+#[derive(Clone, Debug)]
+pub struct GroundMotion {
+    /// Speed of the sub-satellite point over the ground in km/s.
+    pub ground_speed: f64,
+    /// Direction of the ground track, clockwise from north in deg.
+    pub heading: f64,
+    /// Rate of change of the altitude in km/s.
+    pub altitude_rate: f64,
+}
+
+/// Osculating Keplerian elements, describing the two-body orbit through a state.
+///
+/// Unlike the mean elements of a TLE, they include the short-periodic perturbations at the
+/// instant of the state.
+#[derive(Clone, Debug)]
+pub struct KeplerianElements {
+    /// Semi-major axis in km.
+    pub semi_major_axis: f64,
+    pub eccentricity: f64,
+    /// Inclination in deg.
+    pub inclination: f64,
+    /// Right ascension of the ascending node in deg.
+    pub right_ascension: f64,
+    /// Argument of perigee in deg.
+    pub argument_of_perigee: f64,
+    /// True anomaly in deg.
+    pub true_anomaly: f64,
+    /// Mean anomaly in deg.
+    pub mean_anomaly: f64,
+}
+
+impl KeplerianElements {
+    /// Earth gravitational parameter of the WGS72 model used by SGP4 (km³/s²)
+    const MU: f64 = 398600.8;
+
+    /// Computes the elements from an inertial position in km and velocity in km/s.
+    ///
+    /// For circular orbits the argument of perigee is zero and the anomalies are measured from
+    /// the ascending node, for equatorial orbits the ascending node is taken on the x axis.
+    pub fn from_state_vector(position: [f64; 3], velocity: [f64; 3]) -> Self {
+        const EPSILON: f64 = 1e-9;
+
+        let r = norm(position);
+        let v = norm(velocity);
+        let radial_velocity = dot(position, velocity);
+
+        let h = cross(position, velocity);
+        // Towards the ascending node
+        let n = [-h[1], h[0], 0.0];
+        let e = {
+            let a = v * v - Self::MU / r;
+            [0, 1, 2].map(|i| (a * position[i] - radial_velocity * velocity[i]) / Self::MU)
+        };
+        let eccentricity = norm(e);
+
+        let semi_major_axis = 1.0 / (2.0 / r - v * v / Self::MU);
+        let inclination = (h[2] / norm(h)).clamp(-1.0, 1.0).acos();
+
+        let node = if norm(n) > EPSILON {
+            n
+        } else {
+            [1.0, 0.0, 0.0]
+        };
+        let right_ascension = node[1].atan2(node[0]).rem_euclid(2.0 * PI);
+
+        // Angle from `from` to `to` in the orbit plane, in the direction of motion
+        let angle = |from: [f64; 3], to: [f64; 3]| {
+            let angle = (dot(from, to) / (norm(from) * norm(to)))
+                .clamp(-1.0, 1.0)
+                .acos();
+            if dot(cross(from, to), h) < 0.0 {
+                2.0 * PI - angle
+            } else {
+                angle
+            }
+        };
+        let (argument_of_perigee, true_anomaly) = if eccentricity > EPSILON {
+            (angle(node, e), angle(e, position))
+        } else {
+            (0.0, angle(node, position))
+        };
+
+        let eccentric_anomaly = 2.0
+            * (((1.0 - eccentricity) / (1.0 + eccentricity)).sqrt() * (true_anomaly / 2.0).tan())
+                .atan();
+        let mean_anomaly =
+            (eccentric_anomaly - eccentricity * eccentric_anomaly.sin()).rem_euclid(2.0 * PI);
+
+        Self {
+            semi_major_axis,
+            eccentricity,
+            inclination: inclination.to_degrees(),
+            right_ascension: right_ascension.to_degrees(),
+            argument_of_perigee: argument_of_perigee.to_degrees(),
+            true_anomaly: true_anomaly.to_degrees(),
+            mean_anomaly: mean_anomaly.to_degrees(),
+        }
+    }
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn norm(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+/// WGS84 Earth semi-major axis (km)
+const EARTH_EQUATORIAL_RADIUS: f64 = 6378.137;
+
+/// Earth's rotation rate relative to the mean equinox (rad/s)
+const EARTH_ROTATION_RATE: f64 = 7.292115e-5;
+
 /// Returns the Julian days for the given UTC datetime.
-fn julian_days_from_utc(datetime: DateTime<Utc>) -> f64 {
+pub fn julian_days_from_utc(datetime: DateTime<Utc>) -> f64 {
     let year = datetime.year();
     let month = datetime.month() as i32;
     let day = datetime.day() as i32;
@@ -199,7 +611,7 @@ fn julian_days_from_utc(datetime: DateTime<Utc>) -> f64 {
 /// # Returns
 ///
 /// The GMST in radians, normalized to [0, 2π]
-fn gmst_from_julian_days(julian_days: f64) -> f64 {
+pub fn gmst_from_julian_days(julian_days: f64) -> f64 {
     // Constants
     const J2000_EPOCH: f64 = 2451545.0; // Julian Date for J2000.0 epoch
     const JULIAN_CENTURY: f64 = 36525.0; // Days in a Julian century
@@ -277,3 +689,28 @@ fn ecef_to_lat_lon_alt(position: [f64; 3]) -> [f64; 3] {
 
     [latitude, longitude, altitude]
 }
+
+/// Converts geodetic coordinates (latitude, longitude, altitude) to a position vector in Earth-Centered Earth-Fixed (ECEF) frame
+///
+/// # Arguments
+/// * `position` - An array [latitude, longitude, altitude] in degrees and kilometers
+///
+/// # Returns
+/// A 3D position vector [x, y, z] in the ECEF frame (kilometers)
+pub fn lat_lon_alt_to_ecef(position: [f64; 3]) -> [f64; 3] {
+    const A: f64 = 6378.137; // WGS84 Earth semi-major axis (km)
+    const F: f64 = 1.0 / 298.257223563; // Flattening
+
+    let [latitude, longitude, altitude] = position;
+    let (sin_lat, cos_lat) = latitude.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = longitude.to_radians().sin_cos();
+
+    let e2 = F * (2.0 - F); // Square of first eccentricity
+    let n = A / (1.0 - e2 * sin_lat.powi(2)).sqrt(); // Prime vertical radius of curvature
+
+    [
+        (n + altitude) * cos_lat * cos_lon,
+        (n + altitude) * cos_lat * sin_lon,
+        (n * (1.0 - e2) + altitude) * sin_lat,
+    ]
+}
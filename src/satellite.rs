@@ -1,5 +1,6 @@
-use std::time::Duration;
+use std::{future::Future, time::Duration};
 
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use strum::{Display, EnumIter};
 use tokio::fs;
 use ureq::serde_json;
@@ -91,17 +92,41 @@ impl Satellite {
     /// If cache is older than 2 hours, fetches elements from celestrak.org.
     /// Otherwise, reads elements from cache.
     pub async fn get_elements(&self) -> Option<Vec<sgp4::Elements>> {
+        self.get_cached("json", || self.fetch_elements()).await
+    }
+
+    /// Returns SATCAT records.
+    ///
+    /// If cache is older than 2 hours, fetches records from celestrak.org.
+    /// Otherwise, reads records from cache.
+    pub async fn get_satcat_records(&self) -> Option<Vec<SatcatRecord>> {
+        self.get_cached("satcat.json", || self.fetch_satcat_records())
+            .await
+    }
+
+    /// Returns the cached data with the given extension, fetching it if the cache is missing or
+    /// older than 2 hours.
+    async fn get_cached<T, F, Fut>(&self, extension: &str, fetch: F) -> Option<T>
+    where
+        T: Serialize + DeserializeOwned,
+        F: Fn() -> Fut,
+        Fut: Future<Output = Option<T>>,
+    {
         let cache_path = dirs::cache_dir()
             .expect("failed to get cache directory")
-            .join(format!("tracker/{}.json", self.to_string().to_lowercase()));
+            .join(format!(
+                "tracker/{}.{}",
+                self.to_string().to_lowercase(),
+                extension
+            ));
         fs::create_dir_all(cache_path.parent().unwrap())
             .await
             .unwrap();
 
-        // Fetch elements if cache doesn't exist
+        // Fetch data if cache doesn't exist
         if !std::fs::exists(&cache_path).unwrap() {
-            if let Some(elements) = self.fetch_elements().await {
-                fs::write(&cache_path, serde_json::to_string(&elements).unwrap())
+            if let Some(data) = fetch().await {
+                fs::write(&cache_path, serde_json::to_string(&data).unwrap())
                     .await
                     .unwrap();
             } else {
@@ -118,10 +143,10 @@ impl Satellite {
             .unwrap();
         let is_cache_expired = age > Duration::from_secs(2 * 60 * 60);
 
-        // Fetch elements if cache is older than 2 hours
+        // Fetch data if cache is older than 2 hours
         if is_cache_expired {
-            if let Some(elements) = self.fetch_elements().await {
-                fs::write(&cache_path, serde_json::to_string(&elements).unwrap())
+            if let Some(data) = fetch().await {
+                fs::write(&cache_path, serde_json::to_string(&data).unwrap())
                     .await
                     .unwrap();
             }
@@ -151,4 +176,35 @@ impl Satellite {
             })
             .ok()
     }
+
+    /// Fetches SATCAT records from celestrak.org.
+    async fn fetch_satcat_records(&self) -> Option<Vec<SatcatRecord>> {
+        let mut request =
+            ureq::get("https://celestrak.org/satcat/records.php").query("FORMAT", "json");
+
+        request = match (self.cospar_id(), self.group()) {
+            (Some(id), None) => request.query("INTDES", id),
+            (None, Some(group)) => request.query("GROUP", group),
+            _ => unreachable!(),
+        };
+
+        request
+            .call()
+            .ok()
+            .and_then(|response| response.into_json().ok())
+    }
+}
+
+/// Satellite catalog (SATCAT) record of an object.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct SatcatRecord {
+    /// The NORAD ID of the object.
+    pub norad_cat_id: u64,
+    /// The type of the object, e.g. `PAY`, `R/B`, `DEB` or `UNK`.
+    #[serde(default)]
+    pub object_type: Option<String>,
+    /// The owner or country of origin of the object, e.g. `US` or `PRC`.
+    #[serde(default)]
+    pub owner: Option<String>,
 }
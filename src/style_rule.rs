@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use ratatui::style::Color;
+use serde::Deserialize;
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+use crate::object::{Object, Staleness};
+
+/// Colours used for rules without a fixed colour for each category.
+const PALETTE: [Color; 12] = [
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+];
+
+/// Property by which objects are colour coded.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Display, EnumIter, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StyleRule {
+    #[default]
+    Group,
+    #[strum(to_string = "Orbit regime")]
+    OrbitRegime,
+    #[strum(to_string = "Object type")]
+    ObjectType,
+    Country,
+    #[strum(to_string = "Element age")]
+    ElementAge,
+}
+
+/// Category of an object under a [`StyleRule`].
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct Category {
+    /// Position of the category in the legend.
+    pub rank: usize,
+    pub label: String,
+}
+
+impl StyleRule {
+    /// Returns the rule after this one, wrapping around.
+    pub fn next(self) -> Self {
+        Self::iter()
+            .cycle()
+            .skip_while(|rule| *rule != self)
+            .nth(1)
+            .unwrap()
+    }
+
+    /// Returns the category of the object at the given time.
+    pub fn categorize(self, object: &Object, time: DateTime<Utc>) -> Category {
+        match self {
+            Self::Group => Category {
+                rank: object.group() as usize,
+                label: object.group().to_string(),
+            },
+            Self::OrbitRegime => Category {
+                rank: object.orbit_regime() as usize,
+                label: object.orbit_regime().to_string(),
+            },
+            Self::ObjectType => Category {
+                rank: object.object_type() as usize,
+                label: object.object_type().to_string(),
+            },
+            Self::Country => Category {
+                rank: 0,
+                label: object.owner().unwrap_or("Unknown").to_string(),
+            },
+            Self::ElementAge => {
+                let staleness = Staleness::from_age(object.element_age(time));
+                Category {
+                    rank: staleness as usize,
+                    label: staleness.to_string(),
+                }
+            }
+        }
+    }
+
+    /// Returns the colour of the category.
+    ///
+    /// # Arguments
+    /// * `category` - The category to colour
+    /// * `index` - Position of the category among all categories present
+    pub fn color(self, category: &Category, index: usize) -> Color {
+        match self {
+            Self::Group | Self::Country => PALETTE[index % PALETTE.len()],
+            Self::OrbitRegime | Self::ObjectType | Self::ElementAge => [
+                Color::LightGreen,
+                Color::LightYellow,
+                Color::LightRed,
+                Color::LightMagenta,
+            ][category.rank],
+        }
+    }
+}
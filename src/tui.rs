@@ -6,6 +6,7 @@ use crossterm::{
     terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::Backend, Terminal};
+use ratatui_image::picker::{Picker, ProtocolType};
 
 use crate::event::EventHandler;
 
@@ -19,21 +20,37 @@ pub struct Tui<B: Backend> {
     pub terminal: Terminal<B>,
     /// Terminal event handler.
     pub events: EventHandler,
+    /// Image renderer, if the terminal supports a graphics protocol.
+    pub picker: Option<Picker>,
 }
 
 impl<B: Backend> Tui<B> {
     /// Constructs a new instance of [`Tui`].
     pub fn new(terminal: Terminal<B>, events: EventHandler) -> Self {
-        Self { terminal, events }
+        Self {
+            terminal,
+            events,
+            picker: None,
+        }
     }
 
     /// Initializes the terminal interface.
     ///
-    /// It enables the raw mode and sets terminal properties.
-    pub fn init(&mut self) -> Result<()> {
+    /// It enables the raw mode and sets terminal properties. If `detect_graphics` is set, the
+    /// terminal is queried for graphics protocol support, and [`Tui::picker`] is set if any is
+    /// supported.
+    pub fn init(&mut self, detect_graphics: bool) -> Result<()> {
         terminal::enable_raw_mode()?;
         crossterm::execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
 
+        // Query the terminal before the event handler starts reading its responses.
+        if detect_graphics {
+            self.picker = Picker::from_query_stdio()
+                .ok()
+                .filter(|picker| picker.protocol_type() != ProtocolType::Halfblocks);
+        }
+        self.events.start();
+
         // Set panic hook to reset the terminal interface on panic.
         let panic_hook = panic::take_hook();
         panic::set_hook(Box::new(move |panic| {
@@ -0,0 +1,164 @@
+use chrono::{DateTime, Duration, Local, Utc};
+use serde::Deserialize;
+
+/// Units and formats in which values are displayed.
+#[derive(Clone, Copy, Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct Units {
+    pub length: LengthUnit,
+    pub angle: AngleFormat,
+    pub period: PeriodFormat,
+    pub time_zone: TimeZone,
+}
+
+/// Unit of distances and speeds.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LengthUnit {
+    /// Kilometres.
+    #[default]
+    Metric,
+    /// Statute miles.
+    Imperial,
+}
+
+/// Format of angles.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AngleFormat {
+    /// Decimal degrees, e.g. `51.4769°`.
+    #[default]
+    Decimal,
+    /// Degrees, minutes and seconds, e.g. `51°28'36.8"`.
+    Dms,
+}
+
+/// Format of orbital periods.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeriodFormat {
+    /// Decimal minutes, e.g. `92.68 min`.
+    #[default]
+    Minutes,
+    /// Hours, minutes and seconds, e.g. `01:32:41`.
+    Hms,
+}
+
+/// Time zone of dates and times.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeZone {
+    #[default]
+    Utc,
+    /// The time zone of the system.
+    Local,
+}
+
+impl Units {
+    const KM_PER_MILE: f64 = 1.609344;
+
+    /// Formats a distance given in km.
+    pub fn distance(&self, km: f64, precision: usize) -> String {
+        match self.length {
+            LengthUnit::Metric => format!("{:.*} km", precision, km),
+            LengthUnit::Imperial => format!("{:.*} mi", precision, km / Self::KM_PER_MILE),
+        }
+    }
+
+    /// Formats a speed given in km/s.
+    pub fn speed(&self, km_per_s: f64, precision: usize) -> String {
+        match self.length {
+            LengthUnit::Metric => format!("{:.*} km/s", precision, km_per_s),
+            LengthUnit::Imperial => {
+                format!("{:.*} mi/s", precision, km_per_s / Self::KM_PER_MILE)
+            }
+        }
+    }
+
+    /// Formats an angle given in degrees.
+    ///
+    /// `precision` is the number of decimals of a decimal angle, DMS angles are given to a
+    /// similar resolution.
+    pub fn angle(&self, degrees: f64, precision: usize) -> String {
+        match self.angle {
+            AngleFormat::Decimal => format!("{:.*}°", precision, degrees),
+            AngleFormat::Dms => {
+                let sign = if degrees < 0.0 { "-" } else { "" };
+                format!("{}{}", sign, dms(degrees.abs(), precision))
+            }
+        }
+    }
+
+    /// Formats a longitude given in degrees.
+    pub fn longitude(&self, degrees: f64, precision: usize) -> String {
+        self.coordinate(degrees, precision, ['E', 'W'])
+    }
+
+    /// Formats a latitude given in degrees.
+    pub fn latitude(&self, degrees: f64, precision: usize) -> String {
+        self.coordinate(degrees, precision, ['N', 'S'])
+    }
+
+    /// Formats an orbital period.
+    pub fn period(&self, period: Duration, precision: usize) -> String {
+        match self.period {
+            PeriodFormat::Minutes => {
+                format!("{:.*} min", precision, period.num_seconds() as f64 / 60.0)
+            }
+            PeriodFormat::Hms => {
+                let seconds = period.num_seconds();
+                format!(
+                    "{:02}:{:02}:{:02}",
+                    seconds / 3600,
+                    seconds / 60 % 60,
+                    seconds % 60
+                )
+            }
+        }
+    }
+
+    /// Formats a date and time.
+    pub fn time(&self, time: DateTime<Utc>) -> String {
+        const FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+        match self.time_zone {
+            TimeZone::Utc => time.format(FORMAT).to_string(),
+            TimeZone::Local => time.with_timezone(&Local).format(FORMAT).to_string(),
+        }
+    }
+
+    /// Formats a longitude or latitude, with a hemisphere letter if in DMS.
+    fn coordinate(&self, degrees: f64, precision: usize, hemispheres: [char; 2]) -> String {
+        match self.angle {
+            // Padded to keep the decimal points of longitudes and latitudes aligned
+            AngleFormat::Decimal => format!("{:>1$.2$}°", degrees, precision + 5, precision),
+            AngleFormat::Dms => {
+                let hemisphere = if degrees < 0.0 {
+                    hemispheres[1]
+                } else {
+                    hemispheres[0]
+                };
+                format!(
+                    "{:>1$} {2}",
+                    dms(degrees.abs(), precision),
+                    precision + 8,
+                    hemisphere
+                )
+            }
+        }
+    }
+}
+
+/// Formats a non-negative angle in degrees, minutes and seconds.
+///
+/// The seconds are given with 3 fewer decimals than the `precision` of the decimal degrees.
+fn dms(degrees: f64, precision: usize) -> String {
+    let decimals = precision.saturating_sub(3);
+    let scale = 10_u64.pow(decimals as u32);
+    // Round once to carry into minutes and degrees
+    let total = (degrees * 3600.0 * scale as f64).round() as u64;
+    let (degrees, rest) = (total / (3600 * scale), total % (3600 * scale));
+    let (minutes, seconds) = (rest / (60 * scale), rest % (60 * scale));
+    let seconds = seconds as f64 / scale as f64;
+    let width = if decimals == 0 { 2 } else { decimals + 3 };
+    format!("{}°{:02}'{:0width$.decimals$}\"", degrees, minutes, seconds)
+}
@@ -0,0 +1,264 @@
+use anyhow::Result;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Margin, Position, Rect},
+    style::{palette::tailwind, Color, Modifier, Style, Stylize},
+    symbols::Marker,
+    text::{Line, Text},
+    widgets::{
+        Axis, Block, Cell, Chart, Dataset, GraphType, Row, Scrollbar, ScrollbarState,
+        StatefulWidget, Table, TableState, Widget,
+    },
+};
+
+use crate::{app::App, keymap::Action, object::OrbitRegime};
+
+use super::{
+    border_style, legend::LegendState, navigate, satellites::SatellitesState,
+    world_map::WorldMapState,
+};
+
+/// Longitudes and drift rates of the geostationary objects.
+pub struct GeoBelt<'a> {
+    pub satellites_state: &'a SatellitesState,
+    pub world_map_state: &'a WorldMapState,
+    pub legend_state: &'a LegendState,
+    /// Whether the view has the keyboard focus.
+    pub focused: bool,
+}
+
+#[derive(Default)]
+pub struct GeoBeltState {
+    /// Indices of the listed objects, from west to east.
+    pub objects: Vec<usize>,
+    pub table_state: TableState,
+    pub inner_area: Rect,
+}
+
+impl GeoBeltState {
+    /// Maximum drift rate of an object considered to be kept on station, in deg/day.
+    pub const STATION_KEEPING_DRIFT: f64 = 0.1;
+}
+
+impl GeoBelt<'_> {
+    fn render_block(&self, area: Rect, buf: &mut Buffer, state: &GeoBeltState) {
+        let title = format!("GEO belt ({} objects)", state.objects.len());
+        Block::bordered()
+            .title(title.blue())
+            .border_style(border_style(self.focused))
+            .render(area, buf);
+    }
+
+    /// Plots the drift rate of the objects against their longitude.
+    fn render_chart(&self, area: Rect, buf: &mut Buffer, state: &GeoBeltState) {
+        let objects = &self.satellites_state.objects;
+        let states = &self.satellites_state.states;
+        let point = |index: usize| (states[index].longitude(), objects[index].drift_rate());
+
+        let (selected, others): (Vec<usize>, Vec<usize>) = state
+            .objects
+            .iter()
+            .partition(|&&index| self.world_map_state.is_selected(index));
+        let (stationary, drifting): (Vec<_>, Vec<_>) = others
+            .into_iter()
+            .map(point)
+            .partition(|(_, drift)| drift.abs() < GeoBeltState::STATION_KEEPING_DRIFT);
+        let selected: Vec<(f64, f64)> = selected.into_iter().map(point).collect();
+
+        // Symmetric about zero drift, at least one degree per day
+        let max_drift = stationary
+            .iter()
+            .chain(&drifting)
+            .chain(&selected)
+            .map(|(_, drift)| drift.abs())
+            .fold(1.0, f64::max)
+            .ceil();
+
+        let dataset = |name: &'static str, data, color: Color| {
+            Dataset::default()
+                .name(name)
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Scatter)
+                .style(Style::new().fg(color))
+                .data(data)
+        };
+        let datasets = vec![
+            dataset("On station", &stationary, Color::LightGreen),
+            dataset("Drifting", &drifting, Color::Yellow),
+            dataset("Selected", &selected, Color::LightRed),
+        ];
+
+        let chart = Chart::new(datasets)
+            .x_axis(
+                Axis::default()
+                    .title("Longitude")
+                    .bounds([-180.0, 180.0])
+                    .labels(["180°W", "90°W", "0°", "90°E", "180°E"])
+                    .style(Style::new().dark_gray()),
+            )
+            .y_axis(
+                Axis::default()
+                    .title("°/day")
+                    .bounds([-max_drift, max_drift])
+                    .labels([
+                        format!("{:+}", -max_drift),
+                        "0".to_string(),
+                        format!("{:+}", max_drift),
+                    ])
+                    .style(Style::new().dark_gray()),
+            )
+            .hidden_legend_constraints((Constraint::Ratio(1, 3), Constraint::Ratio(1, 2)));
+        chart.render(area, buf);
+    }
+
+    fn render_table(&self, area: Rect, buf: &mut Buffer, state: &mut GeoBeltState) {
+        let objects = &self.satellites_state.objects;
+        let states = &self.satellites_state.states;
+
+        let header = Row::new(["Name", "NORAD ID", "Longitude", "Drift", "Inc", "Ecc"]).bold();
+        let rows = state.objects.iter().enumerate().map(|(i, &index)| {
+            let object = &objects[index];
+            let drift = object.drift_rate();
+            let drift = if drift.abs() < GeoBeltState::STATION_KEEPING_DRIFT {
+                format!("{:+.3}°/d", drift).light_green()
+            } else {
+                format!("{:+.3}°/d", drift).yellow()
+            };
+            let color = match i % 2 {
+                0 => tailwind::SLATE.c950,
+                _ => tailwind::SLATE.c900,
+            };
+            let name = if self.world_map_state.is_selected(index) {
+                object.name().clone().light_red()
+            } else {
+                object.name().clone().into()
+            };
+            Row::new([
+                Cell::from(name),
+                Cell::from(object.norad_id().to_string()),
+                Cell::from(format!("{:8.3}°", states[index].longitude())),
+                Cell::from(Text::from(Line::from(drift))),
+                Cell::from(format!("{:.3}°", object.inclination())),
+                Cell::from(format!("{:.5}", object.eccentricity())),
+            ])
+            .style(Style::new().bg(color))
+        });
+
+        let widths = [
+            Constraint::Fill(1),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        state.inner_area = area;
+        StatefulWidget::render(table, area, buf, &mut state.table_state);
+
+        let mut scrollbar_state = ScrollbarState::new(
+            state
+                .objects
+                .len()
+                .saturating_sub(area.height.saturating_sub(1) as usize),
+        )
+        .position(state.table_state.offset());
+        // On the right border of the block
+        let scrollbar_area = Rect::new(area.right(), area.y, 1, area.height);
+        Scrollbar::default().render(scrollbar_area, buf, &mut scrollbar_state);
+    }
+}
+
+impl StatefulWidget for GeoBelt<'_> {
+    type State = GeoBeltState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let objects = &self.satellites_state.objects;
+        let states = &self.satellites_state.states;
+        state.objects = (0..objects.len())
+            .filter(|&index| objects[index].orbit_regime() == OrbitRegime::Geo)
+            .filter(|&index| {
+                !self.legend_state.is_hidden(index) || self.world_map_state.is_selected(index)
+            })
+            .collect();
+        state
+            .objects
+            .sort_by(|&a, &b| states[a].longitude().total_cmp(&states[b].longitude()));
+
+        self.render_block(area, buf, state);
+        let [chart_area, table_area] =
+            Layout::vertical([Constraint::Percentage(50), Constraint::Fill(1)])
+                .areas(area.inner(Margin::new(1, 1)));
+        self.render_chart(chart_area, buf, state);
+        self.render_table(table_area, buf, state);
+    }
+}
+
+pub async fn handle_action(action: Action, app: &mut App) -> Result<()> {
+    let state = &mut app.geo_belt_state;
+    match action {
+        // Select the highlighted object
+        Action::Activate => {
+            let selected = state.table_state.selected();
+            if let Some(&index) = selected.and_then(|row| state.objects.get(row)) {
+                app.world_map_state.selected_objects = vec![index];
+            }
+        }
+        action => {
+            let page = state.inner_area.height.saturating_sub(1) as usize;
+            if let Some(row) = navigate(
+                action,
+                state.table_state.selected(),
+                state.objects.len(),
+                page,
+            ) {
+                state.table_state.select(Some(row));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn handle_mouse_events(event: MouseEvent, app: &mut App) -> Result<()> {
+    let inner_area = app.geo_belt_state.inner_area;
+    if !inner_area.contains(Position::new(event.column, event.row)) {
+        app.geo_belt_state.table_state.select(None);
+        return Ok(());
+    }
+
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            // Select the clicked object.
+            if let Some(row) = app.geo_belt_state.table_state.selected() {
+                app.world_map_state.selected_objects = vec![app.geo_belt_state.objects[row]];
+            }
+        }
+        MouseEventKind::ScrollDown => {
+            let max_offset = app
+                .geo_belt_state
+                .objects
+                .len()
+                .saturating_sub(inner_area.height.saturating_sub(1) as usize);
+            *app.geo_belt_state.table_state.offset_mut() =
+                (app.geo_belt_state.table_state.offset() + 1).min(max_offset);
+        }
+        MouseEventKind::ScrollUp => {
+            *app.geo_belt_state.table_state.offset_mut() =
+                app.geo_belt_state.table_state.offset().saturating_sub(1);
+        }
+        _ => {}
+    }
+    // Highlight the hovered row, skipping the header.
+    let row = (event.row - inner_area.y) as usize;
+    let index = row
+        .checked_sub(1)
+        .map(|row| row + app.geo_belt_state.table_state.offset())
+        .filter(|&row| row < app.geo_belt_state.objects.len());
+    app.geo_belt_state.table_state.select(index);
+
+    Ok(())
+}
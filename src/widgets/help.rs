@@ -0,0 +1,148 @@
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    style::{palette::tailwind, Style, Stylize},
+    text::Line,
+    widgets::{
+        Block, Clear, Row, Scrollbar, ScrollbarState, StatefulWidget, Table, TableState, Widget,
+    },
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    app::App,
+    keymap::{Action, Keymap},
+};
+
+use super::navigate;
+
+/// The keys bound to each action, shown over the other panels.
+pub struct Help<'a> {
+    pub keymap: &'a Keymap,
+}
+
+#[derive(Default)]
+pub struct HelpState {
+    pub table_state: TableState,
+    pub inner_area: Rect,
+}
+
+impl Help<'_> {
+    /// Returns the hint at the bottom of the overlay, naming the scroll keys if the bindings
+    /// don't fit.
+    fn hint(&self, scrollable: bool) -> String {
+        let up = self.keymap.keys(Action::Up).first();
+        let down = self.keymap.keys(Action::Down).first();
+        match (up, down) {
+            (Some(up), Some(down)) if scrollable => {
+                format!(" {}/{} to scroll, any other key to close ", up, down)
+            }
+            _ => " Press any key to close ".to_string(),
+        }
+    }
+}
+
+impl StatefulWidget for Help<'_> {
+    type State = HelpState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let bindings: Vec<_> = self
+            .keymap
+            .bindings()
+            .iter()
+            .map(|(action, keys)| {
+                let keys: Vec<_> = keys.iter().map(ToString::to_string).collect();
+                (keys.join(", "), action.description())
+            })
+            .collect();
+
+        let keys_width = bindings
+            .iter()
+            .map(|(keys, _)| keys.width())
+            .max()
+            .unwrap_or(0) as u16;
+        let description_width = bindings
+            .iter()
+            .map(|(_, description)| description.width())
+            .max()
+            .unwrap_or(0) as u16;
+        // Borders and column spacing
+        let width = keys_width + description_width + 3;
+        let height = bindings.len() as u16 + 2;
+        // Centred, and scrolled if taller than the terminal
+        let [help_area] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [help_area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(help_area);
+
+        let block = Block::bordered().title("Key bindings".blue());
+        state.inner_area = block.inner(help_area);
+        let max_offset = bindings
+            .len()
+            .saturating_sub(state.inner_area.height as usize);
+        *state.table_state.offset_mut() = state.table_state.offset().min(max_offset);
+        let block =
+            block.title_bottom(Line::from(self.hint(max_offset > 0).dark_gray()).right_aligned());
+
+        let rows = bindings
+            .into_iter()
+            .enumerate()
+            .map(|(i, (keys, description))| {
+                let color = match i % 2 {
+                    0 => tailwind::SLATE.c950,
+                    _ => tailwind::SLATE.c900,
+                };
+                Row::new([keys.bold(), description.into()]).style(Style::new().bg(color))
+            });
+        let widths = [
+            Constraint::Length(keys_width),
+            Constraint::Length(description_width),
+        ];
+
+        Clear.render(help_area, buf);
+        StatefulWidget::render(
+            Table::new(rows, widths).block(block),
+            help_area,
+            buf,
+            &mut state.table_state,
+        );
+
+        if max_offset > 0 {
+            let mut scrollbar_state =
+                ScrollbarState::new(max_offset).position(state.table_state.offset());
+            Scrollbar::default().render(
+                help_area.inner(Margin::new(0, 1)),
+                buf,
+                &mut scrollbar_state,
+            );
+        }
+    }
+}
+
+/// Scrolls the key bindings with the navigation keys, and closes them on any other key.
+pub async fn handle_key_events(event: KeyEvent, app: &mut App) -> Result<()> {
+    let state = &mut app.help_state;
+    let page = state.inner_area.height as usize;
+    let max_offset = app.keymap.bindings().len().saturating_sub(page);
+    let offset = app
+        .keymap
+        .action(event)
+        .filter(|_| max_offset > 0)
+        .and_then(|action| {
+            navigate(
+                action,
+                Some(state.table_state.offset()),
+                max_offset + 1,
+                page,
+            )
+        });
+    match offset {
+        Some(offset) => *state.table_state.offset_mut() = offset,
+        None => app.show_help = false,
+    }
+    Ok(())
+}
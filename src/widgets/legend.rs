@@ -0,0 +1,200 @@
+use std::collections::{BTreeMap, HashSet};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Margin, Position, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{
+        Block, List, ListItem, ListState, Scrollbar, ScrollbarState, StatefulWidget, Widget,
+    },
+};
+
+use crate::{
+    app::App,
+    object::Object,
+    style_rule::{Category, StyleRule},
+};
+
+pub struct Legend;
+
+#[derive(Default)]
+pub struct LegendState {
+    /// The rule by which objects are colour coded.
+    pub rule: StyleRule,
+    /// Labels of the categories whose objects are hidden.
+    pub hidden: HashSet<String>,
+
+    pub items: Vec<LegendItem>,
+    /// Index of the legend item of each object.
+    categories: Vec<usize>,
+    pub list_state: ListState,
+
+    pub inner_area: Rect,
+}
+
+pub struct LegendItem {
+    pub label: String,
+    pub color: Color,
+    /// Number of objects in the category.
+    pub count: usize,
+}
+
+impl LegendState {
+    /// Constructs a new instance of [`LegendState`] with the given rule.
+    pub fn new(rule: StyleRule) -> Self {
+        Self {
+            rule,
+            ..Default::default()
+        }
+    }
+
+    /// Categorizes the objects at the given time.
+    pub fn update(&mut self, objects: &[Object], time: DateTime<Utc>) {
+        let categories: Vec<Category> = objects
+            .iter()
+            .map(|object| self.rule.categorize(object, time))
+            .collect();
+
+        let mut counts = BTreeMap::new();
+        for category in &categories {
+            *counts.entry(category).or_insert(0) += 1;
+        }
+        let indices: BTreeMap<&Category, usize> = counts
+            .keys()
+            .enumerate()
+            .map(|(index, category)| (*category, index))
+            .collect();
+
+        self.items = counts
+            .iter()
+            .enumerate()
+            .map(|(index, (category, count))| LegendItem {
+                label: category.label.clone(),
+                color: self.rule.color(category, index),
+                count: *count,
+            })
+            .collect();
+        self.categories = categories
+            .iter()
+            .map(|category| indices[category])
+            .collect();
+    }
+
+    /// Switches to the next rule.
+    pub fn cycle_rule(&mut self, objects: &[Object], time: DateTime<Utc>) {
+        self.rule = self.rule.next();
+        self.hidden.clear();
+        self.update(objects, time);
+    }
+
+    /// Returns the colour of the object at the given index.
+    pub fn color(&self, index: usize) -> Option<Color> {
+        self.categories
+            .get(index)
+            .map(|&category| self.items[category].color)
+    }
+
+    /// Returns `true` if the object at the given index belongs to a hidden category.
+    pub fn is_hidden(&self, index: usize) -> bool {
+        self.categories
+            .get(index)
+            .is_some_and(|&category| self.hidden.contains(&self.items[category].label))
+    }
+}
+
+impl Legend {
+    fn render_block(&self, area: Rect, buf: &mut Buffer, state: &mut LegendState) {
+        let block = Block::bordered().title(format!("Legend ({})", state.rule).blue());
+        state.inner_area = block.inner(area);
+        block.render(area, buf);
+    }
+
+    fn render_list(&self, buf: &mut Buffer, state: &mut LegendState) {
+        let items = state.items.iter().map(|item| {
+            let line = if state.hidden.contains(&item.label) {
+                Line::from(vec![
+                    "□ ".dark_gray(),
+                    format!("{} ({})", item.label, item.count).dark_gray(),
+                ])
+            } else {
+                Line::from(vec![
+                    "■ ".fg(item.color),
+                    format!("{} ({})", item.label, item.count).white(),
+                ])
+            };
+            ListItem::new(line)
+        });
+
+        let list =
+            List::new(items).highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        StatefulWidget::render(list, state.inner_area, buf, &mut state.list_state);
+    }
+
+    fn render_scrollbar(&self, area: Rect, buf: &mut Buffer, state: &mut LegendState) {
+        let inner_area = area.inner(Margin::new(0, 1));
+        let mut scrollbar_state =
+            ScrollbarState::new(state.items.len().saturating_sub(inner_area.height as usize))
+                .position(state.list_state.offset());
+        Scrollbar::default().render(inner_area, buf, &mut scrollbar_state);
+    }
+}
+
+impl StatefulWidget for Legend {
+    type State = LegendState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        self.render_block(area, buf, state);
+        self.render_list(buf, state);
+        self.render_scrollbar(area, buf, state);
+    }
+}
+
+pub async fn handle_mouse_events(event: MouseEvent, app: &mut App) -> Result<()> {
+    let inner_area = app.legend_state.inner_area;
+    if !inner_area.contains(Position::new(event.column, event.row)) {
+        app.legend_state.list_state.select(None);
+        return Ok(());
+    }
+
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            // Toggle the visibility of the clicked category.
+            if let Some(index) = app.legend_state.list_state.selected() {
+                let label = &app.legend_state.items[index].label;
+                if !app.legend_state.hidden.remove(label) {
+                    app.legend_state.hidden.insert(label.clone());
+                }
+                app.world_map_state.hovered_object = None;
+            }
+        }
+        MouseEventKind::ScrollDown => {
+            let max_offset = app
+                .legend_state
+                .items
+                .len()
+                .saturating_sub(inner_area.height as usize);
+            *app.legend_state.list_state.offset_mut() =
+                (app.legend_state.list_state.offset() + 1).min(max_offset);
+        }
+        MouseEventKind::ScrollUp => {
+            *app.legend_state.list_state.offset_mut() =
+                app.legend_state.list_state.offset().saturating_sub(1);
+        }
+        _ => {}
+    }
+    // Highlight the hovered item.
+    let row = (event.row - inner_area.y) as usize + app.legend_state.list_state.offset();
+    let index = if row < app.legend_state.items.len() {
+        Some(row)
+    } else {
+        None
+    };
+    app.legend_state.list_state.select(index);
+
+    Ok(())
+}
@@ -1,3 +1,44 @@
+use ratatui::style::{Style, Stylize};
+
+use crate::keymap::Action;
+
+pub mod geo_belt;
+pub mod help;
+pub mod legend;
 pub mod object_information;
 pub mod satellites;
+pub mod sky;
+pub mod toast;
 pub mod world_map;
+
+/// Returns the style of the border of a panel, highlighted while it has the keyboard focus.
+pub fn border_style(focused: bool) -> Style {
+    if focused {
+        Style::new().light_blue()
+    } else {
+        Style::new()
+    }
+}
+
+/// Returns the index of the item selected after a navigation action, or `None` if the action
+/// doesn't navigate.
+///
+/// # Arguments
+/// * `action` - The action triggered: moving up or down by an item or a page, or to the first or
+///   last item
+/// * `selected` - Index of the selected item, if any
+/// * `len` - Number of items
+/// * `page` - Number of items visible at once
+pub fn navigate(action: Action, selected: Option<usize>, len: usize, page: usize) -> Option<usize> {
+    let last = len.checked_sub(1)?;
+    let index = match (action, selected) {
+        (Action::Down, Some(index)) => index + 1,
+        (Action::Up, Some(index)) => index.saturating_sub(1),
+        (Action::PageDown, Some(index)) => index + page.max(1),
+        (Action::PageUp, Some(index)) => index.saturating_sub(page.max(1)),
+        (Action::Down | Action::PageDown | Action::First, _) => 0,
+        (Action::Up | Action::PageUp | Action::Last, _) => last,
+        _ => return None,
+    };
+    Some(index.min(last))
+}
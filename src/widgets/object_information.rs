@@ -1,94 +1,270 @@
 use anyhow::Result;
-use arboard::Clipboard;
-use chrono::Utc;
 use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Margin, Position, Rect},
-    style::{palette::tailwind, Modifier, Style, Stylize},
+    style::{palette::tailwind, Color, Modifier, Style, Stylize},
+    symbols::Marker,
     text::Text,
     widgets::{
-        Block, Cell, Paragraph, Row, Scrollbar, ScrollbarState, StatefulWidget, Table, TableState,
-        Widget, Wrap,
+        Axis, Block, Cell, Chart, Dataset, GraphType, Paragraph, Row, Scrollbar, ScrollbarState,
+        Sparkline, StatefulWidget, Table, TableState, Widget, Wrap,
     },
 };
-use reverse_geocoder::ReverseGeocoder;
+use serde::Deserialize;
+use strum::IntoStaticStr;
 use unicode_width::UnicodeWidthStr;
 
-use crate::app::App;
+use crate::{
+    app::App,
+    geolocation::Geolocator,
+    ground_station::GroundStation,
+    keymap::Action,
+    object::{KeplerianElements, Staleness},
+    units::Units,
+};
 
-use super::{satellites::SatellitesState, world_map::WorldMapState};
+use super::{border_style, navigate, satellites::SatellitesState, world_map::WorldMapState};
 
 pub struct ObjectInformation<'a> {
     pub satellites_state: &'a SatellitesState,
     pub world_map_state: &'a WorldMapState,
+    pub geolocator: &'a Geolocator,
+    /// Trajectory colours of the selected objects, in selection order.
+    pub trajectory_colors: &'a [Color],
+    /// Fields shown for a single object, in order.
+    pub fields: &'a [InformationField],
+    pub units: &'a Units,
+    /// The ground station for which the elevation of the object is charted.
+    pub observer: Option<&'a GroundStation>,
+    /// Whether the panel has the keyboard focus.
+    pub focused: bool,
+}
+
+/// A field of the object information table.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, IntoStaticStr, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InformationField {
+    Name,
+    #[strum(serialize = "COSPAR ID")]
+    CosparId,
+    #[strum(serialize = "NORAD ID")]
+    NoradId,
+    Group,
+    Type,
+    Owner,
+    Longitude,
+    Latitude,
+    Altitude,
+    Speed,
+    /// Speed relative to the rotating Earth.
+    #[strum(serialize = "Speed (ECEF)")]
+    EarthFixedSpeed,
+    /// Speed of the sub-satellite point over the ground.
+    #[strum(serialize = "Ground speed")]
+    GroundSpeed,
+    /// Direction of the ground track.
+    Heading,
+    #[strum(serialize = "Alt. rate")]
+    AltitudeRate,
+    Period,
+    Location,
+    Epoch,
+    /// Time elapsed since the epoch, coloured by staleness.
+    #[strum(serialize = "Age")]
+    ElementAge,
+    /// Rough estimate of the position error, growing with the age.
+    #[strum(serialize = "Uncertainty")]
+    PositionUncertainty,
+    #[strum(serialize = "Drag term")]
+    DragTerm,
+    #[strum(serialize = "Inc")]
+    Inclination,
+    #[strum(serialize = "Right asc.")]
+    RightAscension,
+    #[strum(serialize = "Ecc")]
+    Eccentricity,
+    #[strum(serialize = "Arg. perigee")]
+    ArgumentOfPerigee,
+    #[strum(serialize = "M. anomaly")]
+    MeanAnomaly,
+    #[strum(serialize = "M. motion")]
+    MeanMotion,
+    #[strum(serialize = "Rev. #")]
+    RevolutionNumber,
+    Apogee,
+    Perigee,
+    /// Position and velocity in the TEME frame, over several rows.
+    TemeVectors,
+    /// Position and velocity in the ECEF frame, over several rows.
+    EcefVectors,
+    /// Osculating Keplerian elements computed from the TEME state, over several rows.
+    OsculatingElements,
 }
 
+impl InformationField {
+    /// Fields shown unless configured otherwise.
+    pub const DEFAULT: &'static [Self] = &[
+        Self::Name,
+        Self::CosparId,
+        Self::NoradId,
+        Self::Group,
+        Self::Type,
+        Self::Owner,
+        Self::Longitude,
+        Self::Latitude,
+        Self::Altitude,
+        Self::Speed,
+        Self::EarthFixedSpeed,
+        Self::GroundSpeed,
+        Self::Heading,
+        Self::AltitudeRate,
+        Self::Period,
+        Self::Location,
+        Self::Epoch,
+        Self::ElementAge,
+        Self::PositionUncertainty,
+        Self::DragTerm,
+        Self::Inclination,
+        Self::RightAscension,
+        Self::Eccentricity,
+        Self::MeanAnomaly,
+        Self::MeanMotion,
+        Self::RevolutionNumber,
+        Self::TemeVectors,
+        Self::EcefVectors,
+        Self::OsculatingElements,
+    ];
+}
+
+#[derive(Default)]
 pub struct ObjectInformationState {
     pub items: Vec<(&'static str, String)>,
     pub table_state: TableState,
     pub inner_area: Rect,
-    geocoder: ReverseGeocoder,
-}
-
-impl Default for ObjectInformationState {
-    fn default() -> Self {
-        Self {
-            items: Default::default(),
-            table_state: Default::default(),
-            inner_area: Default::default(),
-            geocoder: ReverseGeocoder::new(),
-        }
-    }
 }
 
 impl ObjectInformation<'_> {
+    /// Minimum height of the table of a single object for the charts to be shown below it.
+    const MIN_TABLE_HEIGHT: u16 = 6;
+    /// Height of a sparkline and its title.
+    const SPARKLINE_HEIGHT: u16 = 3;
+    const ELEVATION_CHART_HEIGHT: u16 = 7;
+
     fn render_block(&self, area: Rect, buf: &mut Buffer, state: &mut ObjectInformationState) {
-        let block = Block::bordered().title("Object information".blue());
+        let block = Block::bordered()
+            .title("Object information".blue())
+            .border_style(border_style(self.focused));
         state.inner_area = block.inner(area);
         block.render(area, buf);
     }
 
     fn render_table(&self, buf: &mut Buffer, state: &mut ObjectInformationState, index: usize) {
         let object = &self.satellites_state.objects[index];
-        let object_state = object.predict(Utc::now()).unwrap();
-
-        let result = state
-            .geocoder
-            .search((object_state.latitude(), object_state.longitude()));
-        let city = result.record.name.clone();
-        let country = isocountry::CountryCode::for_alpha2(&result.record.cc)
-            .unwrap()
-            .name();
-
-        state.items = Vec::from([
-            ("Name", object.name().clone()),
-            ("COSPAR ID", object.cospar_id().clone()),
-            ("NORAD ID", object.norad_id().to_string()),
-            ("Longitude", format!("{:9.4}°", object_state.longitude())),
-            ("Latitude", format!("{:9.4}°", object_state.latitude())),
-            ("Altitude", format!("{:.3} km", object_state.altitude())),
-            ("Speed", format!("{:.2} km/s", object_state.speed())),
-            (
-                "Period",
-                format!(
-                    "{:.2} min",
-                    object.orbital_period().num_seconds() as f64 / 60.0
-                ),
-            ),
-            ("Location", format!("{}, {}", city, country)),
-            (
-                "Epoch",
-                object.epoch().format("%Y-%m-%d %H:%M:%S").to_string(),
-            ),
-            ("Drag term", format!("{} 1/ER", object.drag_term())),
-            ("Inc", format!("{}°", object.inclination())),
-            ("Right asc.", format!("{}°", object.right_ascension())),
-            ("Ecc", object.eccentricity().to_string()),
-            ("M. anomaly", format!("{}°", object.mean_anomaly())),
-            ("M. motion", format!("{} 1/day", object.mean_motion())),
-            ("Rev. #", object.revolution_number().to_string()),
-        ]);
+        let object_state = &self.satellites_state.states[index];
+
+        let units = self.units;
+        let time = self.satellites_state.time;
+        let vectors = object.predict_vectors(time).ok();
+        state.items = self
+            .fields
+            .iter()
+            .flat_map(|&field| {
+                let value = match field {
+                    InformationField::Name => object.name().clone(),
+                    InformationField::CosparId => object.cospar_id().clone(),
+                    InformationField::NoradId => object.norad_id().to_string(),
+                    InformationField::Group => object.group().to_string(),
+                    InformationField::Type => object.object_type().to_string(),
+                    InformationField::Owner => object.owner().unwrap_or("Unknown").to_string(),
+                    InformationField::Longitude => units.longitude(object_state.longitude(), 4),
+                    InformationField::Latitude => units.latitude(object_state.latitude(), 4),
+                    InformationField::Altitude => units.distance(object_state.altitude(), 3),
+                    InformationField::Speed => units.speed(object_state.speed(), 2),
+                    InformationField::EarthFixedSpeed => match &vectors {
+                        Some(vectors) => units.speed(vectors.earth_fixed_speed(), 2),
+                        None => return Vec::new(),
+                    },
+                    InformationField::GroundSpeed => match &vectors {
+                        Some(vectors) => units.speed(vectors.ground_motion().ground_speed, 2),
+                        None => return Vec::new(),
+                    },
+                    InformationField::Heading => match &vectors {
+                        Some(vectors) => units.angle(vectors.ground_motion().heading, 1),
+                        None => return Vec::new(),
+                    },
+                    InformationField::AltitudeRate => match &vectors {
+                        Some(vectors) => units.speed(vectors.ground_motion().altitude_rate, 3),
+                        None => return Vec::new(),
+                    },
+                    InformationField::Period => units.period(object.orbital_period(), 2),
+                    InformationField::Location => self
+                        .geolocator
+                        .locate(object_state.longitude(), object_state.latitude())
+                        .describe(units),
+                    InformationField::Epoch => units.time(object.epoch()),
+                    InformationField::ElementAge => format_age(object.element_age(time)),
+                    InformationField::PositionUncertainty => {
+                        format!("±{}", units.distance(object.position_uncertainty(time), 0))
+                    }
+                    InformationField::DragTerm => format!("{} 1/ER", object.drag_term()),
+                    InformationField::Inclination => units.angle(object.inclination(), 4),
+                    InformationField::RightAscension => units.angle(object.right_ascension(), 4),
+                    InformationField::Eccentricity => object.eccentricity().to_string(),
+                    InformationField::ArgumentOfPerigee => {
+                        units.angle(object.argument_of_perigee(), 4)
+                    }
+                    InformationField::MeanAnomaly => units.angle(object.mean_anomaly(), 4),
+                    InformationField::MeanMotion => format!("{} 1/day", object.mean_motion()),
+                    InformationField::RevolutionNumber => object.revolution_number().to_string(),
+                    InformationField::Apogee => units.distance(object.apogee_altitude(), 1),
+                    InformationField::Perigee => units.distance(object.perigee_altitude(), 1),
+                    InformationField::TemeVectors => {
+                        let Some(vectors) = &vectors else {
+                            return Vec::new();
+                        };
+                        return vector_rows(
+                            ["TEME x", "TEME y", "TEME z"],
+                            ["TEME vx", "TEME vy", "TEME vz"],
+                            vectors.teme_position,
+                            vectors.teme_velocity,
+                            units,
+                        );
+                    }
+                    InformationField::EcefVectors => {
+                        let Some(vectors) = &vectors else {
+                            return Vec::new();
+                        };
+                        return vector_rows(
+                            ["ECEF x", "ECEF y", "ECEF z"],
+                            ["ECEF vx", "ECEF vy", "ECEF vz"],
+                            vectors.ecef_position,
+                            vectors.ecef_velocity,
+                            units,
+                        );
+                    }
+                    InformationField::OsculatingElements => {
+                        let Some(vectors) = &vectors else {
+                            return Vec::new();
+                        };
+                        let elements = KeplerianElements::from_state_vector(
+                            vectors.teme_position,
+                            vectors.teme_velocity,
+                        );
+                        return Vec::from([
+                            ("Osc. a", units.distance(elements.semi_major_axis, 3)),
+                            ("Osc. e", format!("{:.7}", elements.eccentricity)),
+                            ("Osc. i", units.angle(elements.inclination, 4)),
+                            ("Osc. Ω", units.angle(elements.right_ascension, 4)),
+                            ("Osc. ω", units.angle(elements.argument_of_perigee, 4)),
+                            ("Osc. ν", units.angle(elements.true_anomaly, 4)),
+                            ("Osc. M", units.angle(elements.mean_anomaly, 4)),
+                        ]);
+                    }
+                };
+                vec![(field.into(), value)]
+            })
+            .collect();
 
         let (max_key_width, _max_value_width) = state
             .items
@@ -104,11 +280,19 @@ impl ObjectInformation<'_> {
             .map(|rect| rect.width);
         let right = right.saturating_sub(1) as usize;
 
+        let age_key: &str = InformationField::ElementAge.into();
+        let age_color = Staleness::from_age(object.element_age(time)).color();
+
         let rows = state.items.iter().enumerate().map(|(i, (key, value))| {
             let color = match i % 2 {
                 0 => tailwind::SLATE.c950,
                 _ => tailwind::SLATE.c900,
             };
+            let value_style = if *key == age_key {
+                Style::new().fg(age_color)
+            } else {
+                Style::new()
+            };
             let value = if value.width() > right {
                 let etc = "…";
                 let end = value
@@ -122,7 +306,7 @@ impl ObjectInformation<'_> {
             };
             Row::new([
                 Cell::from(Text::from(key.bold())),
-                Cell::from(Text::from(value)),
+                Cell::from(Text::from(value).style(value_style)),
             ])
             .style(Style::new().bg(color))
             .height(1)
@@ -134,6 +318,121 @@ impl ObjectInformation<'_> {
         StatefulWidget::render(table, state.inner_area, buf, &mut state.table_state);
     }
 
+    /// Renders the selected objects side by side.
+    fn render_comparison(
+        &self,
+        buf: &mut Buffer,
+        state: &mut ObjectInformationState,
+        indices: &[usize],
+    ) {
+        let objects: Vec<_> = indices
+            .iter()
+            .map(|&index| &self.satellites_state.objects[index])
+            .collect();
+        let object_states: Vec<_> = indices
+            .iter()
+            .map(|&index| &self.satellites_state.states[index])
+            .collect();
+
+        let rows: Vec<(&'static str, Vec<String>)> = Vec::from([
+            (
+                "NORAD ID",
+                objects.iter().map(|o| o.norad_id().to_string()).collect(),
+            ),
+            (
+                "Longitude",
+                object_states
+                    .iter()
+                    .map(|s| self.units.longitude(s.longitude(), 2))
+                    .collect(),
+            ),
+            (
+                "Latitude",
+                object_states
+                    .iter()
+                    .map(|s| self.units.latitude(s.latitude(), 2))
+                    .collect(),
+            ),
+            (
+                "Altitude",
+                object_states
+                    .iter()
+                    .map(|s| self.units.distance(s.altitude(), 1))
+                    .collect(),
+            ),
+            (
+                "Speed",
+                object_states
+                    .iter()
+                    .map(|s| self.units.speed(s.speed(), 2))
+                    .collect(),
+            ),
+            (
+                "Period",
+                objects
+                    .iter()
+                    .map(|o| self.units.period(o.orbital_period(), 1))
+                    .collect(),
+            ),
+            (
+                "Inc",
+                objects
+                    .iter()
+                    .map(|o| self.units.angle(o.inclination(), 2))
+                    .collect(),
+            ),
+            (
+                "Right asc.",
+                objects
+                    .iter()
+                    .map(|o| self.units.angle(o.right_ascension(), 2))
+                    .collect(),
+            ),
+            (
+                "Ecc",
+                objects
+                    .iter()
+                    .map(|o| format!("{:.4}", o.eccentricity()))
+                    .collect(),
+            ),
+        ]);
+
+        // Values of a row are copied tab-separated.
+        state.items = rows
+            .iter()
+            .map(|(key, values)| (*key, values.join("\t")))
+            .collect();
+
+        let max_key_width = rows.iter().map(|(key, _)| key.width()).max().unwrap_or(0);
+        let widths = std::iter::once(Constraint::Length(max_key_width as u16))
+            .chain(indices.iter().map(|_| Constraint::Fill(1)));
+
+        let header = Row::new(std::iter::once(Cell::from("")).chain(
+            objects.iter().enumerate().map(|(i, object)| {
+                let color = self.trajectory_colors[i % self.trajectory_colors.len()];
+                Cell::from(object.name().clone().fg(color).bold())
+            }),
+        ));
+        let rows = rows.into_iter().enumerate().map(|(i, (key, values))| {
+            let color = match i % 2 {
+                0 => tailwind::SLATE.c950,
+                _ => tailwind::SLATE.c900,
+            };
+            Row::new(
+                std::iter::once(Cell::from(Text::from(key.bold())))
+                    .chain(values.into_iter().map(Cell::from)),
+            )
+            .style(Style::new().bg(color))
+            .height(1)
+        });
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        StatefulWidget::render(table, state.inner_area, buf, &mut state.table_state);
+    }
+
     fn render_scrollbar(&self, area: Rect, buf: &mut Buffer, state: &mut ObjectInformationState) {
         let inner_area = area.inner(Margin::new(0, 1));
         let mut scrollbar_state =
@@ -142,6 +441,120 @@ impl ObjectInformation<'_> {
         Scrollbar::default().render(inner_area, buf, &mut scrollbar_state);
     }
 
+    /// Returns the height of the charts below the table of a single object.
+    fn charts_height(&self) -> u16 {
+        let elevation_height = if self.observer.is_some() {
+            Self::ELEVATION_CHART_HEIGHT
+        } else {
+            0
+        };
+        2 * Self::SPARKLINE_HEIGHT + elevation_height
+    }
+
+    /// Renders the altitude and speed over the next orbit, and the elevation over the next hours
+    /// as seen from the observer.
+    fn render_charts(&self, area: Rect, buf: &mut Buffer, index: usize) {
+        let object = &self.satellites_state.objects[index];
+        let time = self.satellites_state.time;
+        let color = self.trajectory_colors[0];
+
+        let [altitude_area, speed_area, elevation_area] = Layout::vertical([
+            Constraint::Length(Self::SPARKLINE_HEIGHT),
+            Constraint::Length(Self::SPARKLINE_HEIGHT),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+
+        // One sample for each column over the next orbit
+        let samples = altitude_area.width.max(1) as i32;
+        let step = object.orbital_period() / samples;
+        let states: Vec<_> = (0..samples)
+            .filter_map(|i| object.predict(time + step * i).ok())
+            .collect();
+        let altitudes: Vec<_> = states.iter().map(|state| state.altitude()).collect();
+        let speeds: Vec<_> = states.iter().map(|state| state.speed()).collect();
+        render_sparkline(
+            "Altitude",
+            &altitudes,
+            |km| self.units.distance(km, 0),
+            color,
+            altitude_area,
+            buf,
+        );
+        render_sparkline(
+            "Speed",
+            &speeds,
+            |km_per_s| self.units.speed(km_per_s, 2),
+            color,
+            speed_area,
+            buf,
+        );
+
+        if let Some(observer) = self.observer {
+            self.render_elevation_chart(observer, elevation_area, buf, index);
+        }
+    }
+
+    fn render_elevation_chart(
+        &self,
+        observer: &GroundStation,
+        area: Rect,
+        buf: &mut Buffer,
+        index: usize,
+    ) {
+        const HOURS: i64 = 6;
+        const STEP: chrono::Duration = chrono::Duration::minutes(2);
+
+        let object = &self.satellites_state.objects[index];
+        let time = self.satellites_state.time;
+        let color = self.trajectory_colors[0];
+
+        // Below the horizon is drawn along the bottom of the chart
+        let elevations: Vec<(f64, f64)> = (0..=HOURS * 60 / STEP.num_minutes())
+            .filter_map(|i| {
+                let state = object.predict(time + STEP * i as i32).ok()?;
+                let hours = (STEP * i as i32).num_minutes() as f64 / 60.0;
+                Some((hours, observer.look_angles(&state).elevation.max(0.0)))
+            })
+            .collect();
+        let mask = [
+            (0.0, observer.elevation_mask),
+            (HOURS as f64, observer.elevation_mask),
+        ];
+
+        let datasets = vec![
+            Dataset::default()
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::new().dark_gray())
+                .data(&mask),
+            Dataset::default()
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::new().fg(color))
+                .data(&elevations),
+        ];
+        Chart::new(datasets)
+            .block(Block::new().title(format!("Elevation ({})", observer.name).bold()))
+            .x_axis(
+                Axis::default()
+                    .bounds([0.0, HOURS as f64])
+                    .labels([
+                        "now".to_string(),
+                        format!("+{} h", HOURS / 2),
+                        format!("+{} h", HOURS),
+                    ])
+                    .style(Style::new().dark_gray()),
+            )
+            .y_axis(
+                Axis::default()
+                    .bounds([0.0, 90.0])
+                    .labels(["0°", "90°"])
+                    .style(Style::new().dark_gray()),
+            )
+            .render(area, buf);
+    }
+
     fn render_no_object_selected(&self, buf: &mut Buffer, state: &mut ObjectInformationState) {
         let paragraph = Paragraph::new("No object selected".dark_gray())
             .centered()
@@ -156,13 +569,126 @@ impl StatefulWidget for ObjectInformation<'_> {
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         self.render_block(area, buf, state);
-        if let Some(index) = self.world_map_state.selected_object {
-            self.render_table(buf, state, index);
-            self.render_scrollbar(area, buf, state);
-        } else {
-            self.render_no_object_selected(buf, state);
+        match self.world_map_state.selected_objects.as_slice() {
+            [] => self.render_no_object_selected(buf, state),
+            [index] => {
+                let inner_area = state.inner_area;
+                let charts_height = self.charts_height();
+                if inner_area.height >= Self::MIN_TABLE_HEIGHT + charts_height {
+                    let [table_area, charts_area] =
+                        Layout::vertical([Constraint::Fill(1), Constraint::Length(charts_height)])
+                            .areas(inner_area);
+                    state.inner_area = table_area;
+                    self.render_charts(charts_area, buf, *index);
+                }
+                self.render_table(buf, state, *index);
+                // Keep the scrollbar alongside the table
+                let scrollbar_area = Rect {
+                    height: state.inner_area.bottom() + 1 - area.y,
+                    ..area
+                };
+                self.render_scrollbar(scrollbar_area, buf, state);
+            }
+            indices => {
+                self.render_comparison(buf, state, indices);
+                self.render_scrollbar(area, buf, state);
+            }
+        }
+    }
+}
+
+/// Renders a sparkline of the values with their range in the title.
+///
+/// The sparkline spans the range of the values rather than starting from zero, so that small
+/// variations remain visible.
+fn render_sparkline(
+    title: &str,
+    values: &[f64],
+    format: impl Fn(f64) -> String,
+    color: Color,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    const RESOLUTION: f64 = 1000.0;
+
+    if values.is_empty() {
+        return;
+    }
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = (max - min).max(f64::EPSILON);
+    let data: Vec<u64> = values
+        .iter()
+        .map(|value| ((value - min) / range * RESOLUTION).round() as u64)
+        .collect();
+
+    let title = format!("{} {} – {}", title, format(min), format(max));
+    Sparkline::default()
+        .block(Block::new().title(title.bold()))
+        .data(&data)
+        .max(RESOLUTION as u64)
+        .style(Style::new().fg(color))
+        .render(area, buf);
+}
+
+/// Formats the time elapsed since an epoch, e.g. `3 d 04 h ago` or `in 2 h 10 min`.
+fn format_age(age: chrono::Duration) -> String {
+    let minutes = age.num_minutes().abs();
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    let duration = if days > 0 {
+        format!("{} d {:02} h", days, hours)
+    } else {
+        format!("{} h {:02} min", hours, minutes)
+    };
+    if age < chrono::Duration::zero() {
+        format!("in {}", duration)
+    } else {
+        format!("{} ago", duration)
+    }
+}
+
+/// Returns the rows of the components of a position and velocity vector.
+fn vector_rows(
+    position_keys: [&'static str; 3],
+    velocity_keys: [&'static str; 3],
+    position: [f64; 3],
+    velocity: [f64; 3],
+    units: &Units,
+) -> Vec<(&'static str, String)> {
+    let position = position_keys
+        .into_iter()
+        .zip(position)
+        .map(|(key, value)| (key, units.distance(value, 3)));
+    let velocity = velocity_keys
+        .into_iter()
+        .zip(velocity)
+        .map(|(key, value)| (key, units.speed(value, 6)));
+    position.chain(velocity).collect()
+}
+
+pub async fn handle_action(action: Action, app: &mut App) -> Result<()> {
+    let state = &mut app.object_information_state;
+    match action {
+        // Copy the highlighted value to the clipboard
+        Action::Activate => {
+            let selected = state.table_state.selected();
+            if let Some((key, value)) = selected.and_then(|index| state.items.get(index)).cloned() {
+                app.copy_to_clipboard(&value, key);
+            }
+        }
+        action => {
+            let page = state.inner_area.height as usize;
+            if let Some(index) = navigate(
+                action,
+                state.table_state.selected(),
+                state.items.len(),
+                page,
+            ) {
+                state.table_state.select(Some(index));
+            }
         }
     }
+    Ok(())
 }
 
 pub async fn handle_mouse_events(event: MouseEvent, app: &mut App) -> Result<()> {
@@ -176,10 +702,8 @@ pub async fn handle_mouse_events(event: MouseEvent, app: &mut App) -> Result<()>
         MouseEventKind::Down(MouseButton::Left) => {
             // Copy the clicked value to the clipboard.
             if let Some(index) = app.object_information_state.table_state.selected() {
-                if let Ok(mut clipboard) = Clipboard::new() {
-                    let value = app.object_information_state.items[index].1.clone();
-                    clipboard.set_text(value).unwrap();
-                }
+                let (key, value) = app.object_information_state.items[index].clone();
+                app.copy_to_clipboard(&value, key);
             }
         }
         MouseEventKind::ScrollDown => {
@@ -200,14 +724,17 @@ pub async fn handle_mouse_events(event: MouseEvent, app: &mut App) -> Result<()>
         }
         _ => {}
     }
-    // Highlight the hovered row.
-    let row =
-        (event.row - inner_area.y) as usize + app.object_information_state.table_state.offset();
-    let index = if row < app.object_information_state.items.len() {
-        Some(row)
+    // Highlight the hovered row, skipping the header of the comparison table.
+    let header_height = if app.world_map_state.selected_objects.len() > 1 {
+        1
     } else {
-        None
+        0
     };
+    let row = (event.row - inner_area.y) as usize;
+    let index = row
+        .checked_sub(header_height)
+        .map(|row| row + app.object_information_state.table_state.offset())
+        .filter(|&row| row < app.object_information_state.items.len());
     app.object_information_state.table_state.select(index);
 
     Ok(())
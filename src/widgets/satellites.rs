@@ -1,6 +1,7 @@
-use std::time::Instant;
+use std::{collections::HashMap, time::Instant};
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
@@ -13,13 +14,27 @@ use ratatui::{
 };
 use strum::IntoEnumIterator;
 
-use crate::{app::App, object::Object, satellite::Satellite};
+use crate::{
+    app::App,
+    keymap::Action,
+    object::{Object, State},
+    satellite::{SatcatRecord, Satellite},
+};
+
+use super::{border_style, navigate};
 
 #[derive(Default)]
-pub struct Satellites;
+pub struct Satellites {
+    /// Whether the list has the keyboard focus.
+    pub focused: bool,
+}
 
 pub struct SatellitesState {
     pub objects: Vec<Object>,
+    /// Predicted states of the objects at `time`.
+    pub states: Vec<State>,
+    /// The time at which the states were predicted.
+    pub time: DateTime<Utc>,
 
     pub items: Vec<Item>,
     pub list_state: ListState,
@@ -38,12 +53,33 @@ impl SatellitesState {
                 continue;
             }
             if let Some(elements) = item.satellite.get_elements().await {
-                self.objects
-                    .extend(elements.into_iter().map(Object::from_elements));
+                let records: HashMap<u64, SatcatRecord> = item
+                    .satellite
+                    .get_satcat_records()
+                    .await
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|record| (record.norad_cat_id, record))
+                    .collect();
+                self.objects.extend(elements.into_iter().map(|elements| {
+                    let record = records.get(&elements.norad_id);
+                    Object::from_elements(elements, item.satellite, record)
+                }));
             } else {
                 item.selected = false;
             }
         }
+        self.update_states(Utc::now());
+    }
+
+    /// Predicts the states of all objects at the given time.
+    pub fn update_states(&mut self, time: DateTime<Utc>) {
+        self.states = self
+            .objects
+            .iter()
+            .map(|object| object.predict(time).unwrap())
+            .collect();
+        self.time = time;
     }
 }
 
@@ -51,6 +87,8 @@ impl Default for SatellitesState {
     fn default() -> Self {
         Self {
             objects: Vec::new(),
+            states: Vec::new(),
+            time: Utc::now(),
             items: Satellite::iter().map(Item::from).collect(),
             list_state: Default::default(),
             inner_area: Default::default(),
@@ -61,7 +99,9 @@ impl Default for SatellitesState {
 
 impl Satellites {
     fn render_block(&self, area: Rect, buf: &mut Buffer, state: &mut SatellitesState) {
-        let block = Block::bordered().title("Satellites".blue());
+        let block = Block::bordered()
+            .title("Satellites".blue())
+            .border_style(border_style(self.focused));
         state.inner_area = block.inner(area);
         block.render(area, buf);
     }
@@ -120,6 +160,35 @@ impl From<Satellite> for Item {
     }
 }
 
+/// Adds the objects of the group to the map, or removes them if they are shown.
+async fn toggle_item(app: &mut App, index: usize) {
+    app.satellites_state.items[index].selected = !app.satellites_state.items[index].selected;
+    app.world_map_state.selected_objects.clear();
+    app.world_map_state.hovered_object = None;
+    app.satellites_state.refresh_objects().await;
+}
+
+pub async fn handle_action(action: Action, app: &mut App) -> Result<()> {
+    let state = &mut app.satellites_state;
+    match action {
+        // Toggle the highlighted group
+        Action::Activate => {
+            if let Some(index) = state.list_state.selected() {
+                toggle_item(app, index).await;
+            }
+        }
+        action => {
+            let page = state.inner_area.height as usize;
+            if let Some(index) =
+                navigate(action, state.list_state.selected(), state.items.len(), page)
+            {
+                state.list_state.select(Some(index));
+            }
+        }
+    }
+    Ok(())
+}
+
 pub async fn handle_mouse_events(event: MouseEvent, app: &mut App) -> Result<()> {
     let inner_area = app.satellites_state.inner_area;
     if !inner_area.contains(Position::new(event.column, event.row)) {
@@ -131,11 +200,7 @@ pub async fn handle_mouse_events(event: MouseEvent, app: &mut App) -> Result<()>
         MouseEventKind::Down(MouseButton::Left) => {
             // Select the clicked item.
             if let Some(index) = app.satellites_state.list_state.selected() {
-                app.satellites_state.items[index].selected =
-                    !app.satellites_state.items[index].selected;
-                app.world_map_state.selected_object = None;
-                app.world_map_state.hovered_object = None;
-                app.satellites_state.refresh_objects().await;
+                toggle_item(app, index).await;
             }
         }
         MouseEventKind::ScrollDown => {
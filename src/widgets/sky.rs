@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Stylize},
+    text::Text,
+    widgets::{Block, Cell, Row, Table, Widget},
+};
+use strum::IntoEnumIterator;
+
+use crate::{celestial_body::CelestialBody, ground_station::GroundStation};
+
+/// Positions of the Sun and Moon.
+pub struct Sky<'a> {
+    /// The simulation time.
+    pub time: DateTime<Utc>,
+    /// The ground station from which the look angles are computed.
+    pub observer: Option<&'a GroundStation>,
+}
+
+impl Widget for Sky<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let title = match self.observer {
+            Some(observer) => format!("Sun & Moon ({})", observer.name),
+            None => "Sun & Moon".to_string(),
+        };
+        let block = Block::bordered().title(title.blue());
+
+        let header = Row::new(["", "Sub-point", "Az", "El"]).bold();
+        let rows = CelestialBody::iter().map(|body| {
+            let (lon, lat) = body.sub_point(self.time);
+            let (azimuth, elevation) = match self.observer {
+                Some(observer) => {
+                    let look_angles = observer.look_angles_to(body.position(self.time));
+                    let elevation = format!("{:.1}°", look_angles.elevation);
+                    // Dim the body while it is below the horizon
+                    let elevation = if look_angles.elevation < 0.0 {
+                        elevation.dark_gray()
+                    } else {
+                        elevation.into()
+                    };
+                    (format!("{:.1}°", look_angles.azimuth), elevation)
+                }
+                None => ("-".to_string(), "-".into()),
+            };
+            let color = match body {
+                CelestialBody::Sun => Color::Yellow,
+                CelestialBody::Moon => Color::White,
+            };
+            Row::new([
+                Cell::from(Text::from(format!("{} {}", body.symbol(), body).fg(color))),
+                Cell::from(format!("{:.0}°, {:.0}°", lon, lat)),
+                Cell::from(azimuth),
+                Cell::from(Text::from(elevation)),
+            ])
+        });
+
+        let widths = [
+            Constraint::Length(6),
+            Constraint::Fill(1),
+            Constraint::Length(6),
+            Constraint::Length(6),
+        ];
+        Table::new(rows, widths)
+            .header(header)
+            .block(block)
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,84 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    widgets::{Block, Clear, Paragraph, StatefulWidget, Widget},
+};
+use unicode_width::UnicodeWidthStr;
+
+/// A short notification shown in the bottom right corner of the area.
+pub struct Toast;
+
+#[derive(Default)]
+pub struct ToastState {
+    message: Option<Message>,
+}
+
+struct Message {
+    text: String,
+    is_error: bool,
+    shown_at: Instant,
+}
+
+impl ToastState {
+    /// How long a notification stays visible.
+    const DURATION: Duration = Duration::from_secs(3);
+
+    /// Shows a notification of a successful operation.
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.show(text.into(), false);
+    }
+
+    /// Shows a notification of a failed operation.
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.show(text.into(), true);
+    }
+
+    fn show(&mut self, text: String, is_error: bool) {
+        self.message = Some(Message {
+            text,
+            is_error,
+            shown_at: Instant::now(),
+        });
+    }
+}
+
+impl StatefulWidget for Toast {
+    type State = ToastState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        if state
+            .message
+            .as_ref()
+            .is_some_and(|message| message.shown_at.elapsed() >= ToastState::DURATION)
+        {
+            state.message = None;
+        }
+        let Some(message) = &state.message else {
+            return;
+        };
+
+        let width = (message.text.width() as u16 + 4).min(area.width);
+        let height = 3.min(area.height);
+        let toast_area = Rect::new(
+            area.right().saturating_sub(width + 1).max(area.x),
+            area.bottom().saturating_sub(height + 1).max(area.y),
+            width,
+            height,
+        );
+        let color = if message.is_error {
+            Color::LightRed
+        } else {
+            Color::LightGreen
+        };
+
+        Clear.render(toast_area, buf);
+        Paragraph::new(message.text.as_str())
+            .centered()
+            .block(Block::bordered().border_style(Style::new().fg(color)))
+            .fg(color)
+            .render(toast_area, buf);
+    }
+}
@@ -1,126 +1,820 @@
+mod heatmap;
+mod label;
+mod measurement;
+mod raster;
+mod spatial_index;
+mod trajectory;
+mod viewport;
+
+use std::collections::HashMap;
+
 use anyhow::Result;
-use chrono::{Duration, Utc};
-use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use chrono::{DateTime, DurationRound, Utc};
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
     layout::{Position, Rect},
-    style::{Color, Stylize},
+    style::{Color, Style, Stylize},
     widgets::{
-        canvas::{Canvas, Line, Map, MapResolution},
-        Block, StatefulWidget, Widget,
+        canvas::{Canvas, Context, Line, Map, MapResolution, Points},
+        Block, Clear, Paragraph, StatefulWidget, Widget,
     },
 };
+use strum::IntoEnumIterator;
+use unicode_width::UnicodeWidthStr;
 
-use crate::app::App;
+use crate::{
+    app::App,
+    celestial_body::CelestialBody,
+    geodesy,
+    ground_station::GroundStation,
+    keymap::Action,
+    object::{lat_lon_alt_to_ecef, Object, ObjectType, State},
+};
 
-use super::satellites::SatellitesState;
+pub use self::{
+    heatmap::AltitudeBand,
+    measurement::{Endpoint, Measurement},
+    raster::RasterMap,
+    viewport::Viewport,
+};
+
+use self::{
+    heatmap::Heatmap,
+    label::LabelPlacer,
+    spatial_index::SpatialIndex,
+    trajectory::{OrbitEvent, Trajectory},
+};
+use super::{border_style, legend::LegendState, satellites::SatellitesState};
 
 pub struct WorldMap<'a> {
     pub satellites_state: &'a SatellitesState,
+    pub legend_state: &'a LegendState,
+    pub ground_stations: &'a [GroundStation],
+    /// The ground station for which the next pass of the hovered object is predicted.
+    pub observer: Option<&'a GroundStation>,
+    /// NORAD IDs of the objects whose labels are always shown.
+    pub watch_list: &'a [u64],
     pub satellit_symbol: String,
-    pub trajectory_color: Color,
+    /// Trajectory colours of the selected objects, in selection order.
+    pub trajectory_colors: &'a [Color],
+    /// Whether the map has the keyboard focus.
+    pub focused: bool,
+}
+
+/// How objects are drawn on the world map.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub enum MapMode {
+    /// A symbol and label for each object.
+    #[default]
+    Markers,
+    /// Number of objects per region.
+    Heatmap,
 }
 
 #[derive(Default)]
 pub struct WorldMapState {
-    pub selected_object: Option<usize>,
+    /// Indices of the selected objects, in the order they were selected.
+    pub selected_objects: Vec<usize>,
     pub hovered_object: Option<usize>,
+    pub mode: MapMode,
+    /// Altitudes of the objects included in the heatmap.
+    pub altitude_band: AltitudeBand,
+    /// Whether to draw the lines of longitude and latitude.
+    pub show_graticule: bool,
+    /// The region of the world shown.
+    pub viewport: Viewport,
+    /// Whether the viewport keeps the most recently selected object centred.
+    pub follow: bool,
+    /// The measurement tool, while it is active.
+    pub measurement: Option<Measurement>,
+    /// Longitude and latitude under the mouse cursor.
+    pub cursor: Option<(f64, f64)>,
+    /// Name of the nearest place to the mouse cursor.
+    pub cursor_location: Option<String>,
     pub inner_area: Rect,
+    /// Renderer drawing the map as an image, if the terminal supports a graphics protocol.
+    pub raster: Option<RasterMap>,
+    /// Screen positions of the visible objects in the last rendered frame.
+    spatial_index: SpatialIndex,
+    /// Trajectories of the selected objects, by object index.
+    trajectories: HashMap<usize, Trajectory>,
+    /// NORAD ID of the hovered object, the minute its next pass was predicted in and the start of
+    /// the pass.
+    next_pass: Option<(u64, DateTime<Utc>, Option<DateTime<Utc>>)>,
+}
+
+impl WorldMapState {
+    /// Returns the index of the most recently selected object.
+    pub fn selected_object(&self) -> Option<usize> {
+        self.selected_objects.last().copied()
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected_objects.contains(&index)
+    }
+
+    /// Adds the object to the selection, or removes it if it is already selected.
+    pub fn toggle_selection(&mut self, index: usize) {
+        if let Some(position) = self.selected_objects.iter().position(|&i| i == index) {
+            self.selected_objects.remove(position);
+        } else {
+            self.selected_objects.push(index);
+        }
+    }
+
+    /// Returns the NORAD IDs of the selected objects, in the order they were selected.
+    pub fn selected_norad_ids(&self, objects: &[Object]) -> Vec<u64> {
+        self.selected_objects
+            .iter()
+            .map(|&index| objects[index].norad_id())
+            .collect()
+    }
+
+    /// Selects the objects with the given NORAD IDs after the objects were reloaded, dropping the
+    /// ones that are gone.
+    pub fn reselect(&mut self, norad_ids: &[u64], objects: &[Object]) {
+        self.selected_objects = norad_ids
+            .iter()
+            .filter_map(|&norad_id| {
+                objects
+                    .iter()
+                    .position(|object| object.norad_id() == norad_id)
+            })
+            .collect();
+        self.hovered_object = None;
+        // The elements may have changed, as may the indices
+        self.trajectories.clear();
+    }
 }
 
 impl WorldMap<'_> {
-    fn render_block(&self, area: Rect, buf: &mut Buffer, state: &mut WorldMapState) {
-        let block = Block::bordered().title("World map".blue());
+    /// Renders a canvas layer painted by `paint` within the viewport.
+    ///
+    /// The canvas is rendered once for each copy of the world the viewport shows.
+    fn render_layer<F>(&self, buf: &mut Buffer, state: &WorldMapState, paint: F)
+    where
+        F: Fn(&mut Context),
+    {
+        for x_bounds in state.viewport.canvas_x_bounds() {
+            Canvas::default()
+                .paint(&paint)
+                .x_bounds(x_bounds)
+                .y_bounds(state.viewport.y_bounds())
+                .render(state.inner_area, buf);
+        }
+    }
+
+    fn render_block(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut WorldMapState,
+        ground_velocity: Option<(f64, f64)>,
+        positions: &[(f64, f64)],
+    ) {
+        let mut details = Vec::new();
+        if state.mode == MapMode::Heatmap {
+            details.push(format!("density, {}", state.altitude_band));
+        }
+        if let Some(index) = state.follow.then(|| state.selected_object()).flatten() {
+            let object = &self.satellites_state.objects[index];
+            let speed = self.satellites_state.states[index].speed();
+            details.push(format!("following {}", object.name()));
+            if let Some((lon_rate, lat_rate)) = ground_velocity {
+                let latitude = self.satellites_state.states[index].latitude();
+                let heading = (lon_rate * latitude.to_radians().cos())
+                    .atan2(lat_rate)
+                    .to_degrees()
+                    .rem_euclid(360.0);
+                details.push(format!("heading {:03.0}°, {:.2} km/s", heading, speed));
+            }
+        }
+        if state.viewport.zoom() > Viewport::MIN_ZOOM {
+            details.push(format!("×{}", state.viewport.zoom()));
+        }
+        let title = if details.is_empty() {
+            "World map".to_string()
+        } else {
+            format!("World map ({})", details.join(", "))
+        };
+        let mut block = Block::bordered()
+            .title(title.blue())
+            .border_style(border_style(self.focused));
+        if let Some((lon, lat)) = state.cursor {
+            let mut readout = format!(" {:.4}°, {:.4}° ", lon, lat);
+            if let Some(location) = &state.cursor_location {
+                readout += &format!("· {} ", location);
+            }
+            block = block.title_bottom(ratatui::text::Line::from(readout).right_aligned());
+        }
+        if let Some(readout) = self.measurement_readout(state, positions) {
+            block = block.title_bottom(format!(" {} ", readout).light_yellow());
+        }
         state.inner_area = block.inner(area);
         block.render(area, buf);
     }
 
-    fn render_bottom_layer(&self, buf: &mut Buffer, state: &mut WorldMapState) {
-        let bottom_layer = Canvas::default()
-            .paint(|ctx| {
-                // Draw the world map
+    fn render_bottom_layer(
+        &self,
+        buf: &mut Buffer,
+        state: &mut WorldMapState,
+        positions: &[(f64, f64)],
+    ) {
+        self.render_layer(buf, state, |ctx| {
+            // Draw the graticule
+            if state.show_graticule {
+                draw_graticule(ctx, &state.viewport);
+            }
+
+            // Draw the world map
+            ctx.draw(&Map {
+                color: Color::Gray,
+                resolution: MapResolution::High,
+            });
+
+            if state.mode == MapMode::Heatmap {
+                return;
+            }
+
+            // Draw satellites
+            for (index, &(lon, lat)) in positions.iter().enumerate() {
+                if self.legend_state.is_hidden(index) {
+                    continue;
+                }
+                let object = &self.satellites_state.objects[index];
+                let color = self.legend_state.color(index).unwrap_or(Color::LightRed);
+                ctx.print(lon, lat, self.symbol(object).fg(color));
+            }
+        });
+    }
+
+    fn render_heatmap(&self, buf: &mut Buffer, state: &WorldMapState) {
+        let mut heatmap = Heatmap::new(state.inner_area);
+        for (index, object_state) in self.satellites_state.states.iter().enumerate() {
+            if self.legend_state.is_hidden(index)
+                || !state.altitude_band.contains(object_state.altitude())
+            {
+                continue;
+            }
+            if let Some((x, y)) = state.viewport.lon_lat_to_area(
+                object_state.longitude(),
+                object_state.latitude(),
+                state.inner_area,
+            ) {
+                heatmap.add(x, y);
+            }
+        }
+        heatmap.render(buf);
+    }
+
+    fn render_ground_stations(&self, buf: &mut Buffer, state: &mut WorldMapState) {
+        let selected = state
+            .selected_object()
+            .map(|index| &self.satellites_state.states[index]);
+
+        self.render_layer(buf, state, |ctx| {
+            for station in self.ground_stations {
+                let has_line_of_sight =
+                    selected.is_some_and(|state| station.has_line_of_sight(state));
+
+                // Draw the visibility circle for the most recently selected satellite
+                if let Some(selected) = selected {
+                    let color = if has_line_of_sight {
+                        Color::LightGreen
+                    } else {
+                        Color::Yellow
+                    };
+                    draw_path(
+                        ctx,
+                        &state.viewport,
+                        &station.visibility_circle(selected.altitude()),
+                        color,
+                    );
+                }
+
+                let line = if has_line_of_sight {
+                    "▲".light_green().bold() + format!(" {}", station.name).light_green()
+                } else {
+                    "▲".cyan() + format!(" {}", station.name).cyan()
+                };
+                ctx.print(station.longitude, station.latitude, line);
+            }
+        });
+    }
+
+    /// Predicts the trajectories of the selected objects that are missing or out of date.
+    fn update_trajectories(&self, state: &mut WorldMapState) {
+        let time = self.satellites_state.time;
+        state
+            .trajectories
+            .retain(|index, _| state.selected_objects.contains(index));
+        for &index in &state.selected_objects {
+            let object = &self.satellites_state.objects[index];
+            if !state
+                .trajectories
+                .get(&index)
+                .is_some_and(|trajectory| trajectory.is_valid(object, time))
+            {
+                state
+                    .trajectories
+                    .insert(index, Trajectory::predict(object, time));
+            }
+        }
+    }
+
+    /// Renders the points directly below the Sun and Moon.
+    fn render_celestial_bodies(&self, buf: &mut Buffer, state: &WorldMapState) {
+        self.render_layer(buf, state, |ctx| {
+            for body in CelestialBody::iter() {
+                let (lon, lat) = body.sub_point(self.satellites_state.time);
+                let symbol = match body {
+                    CelestialBody::Sun => body.symbol().yellow().bold(),
+                    CelestialBody::Moon => body.symbol().white().bold(),
+                };
+                ctx.print(lon, lat, symbol);
+            }
+        });
+    }
+
+    fn render_top_layer(
+        &self,
+        buf: &mut Buffer,
+        state: &mut WorldMapState,
+        ground_velocity: Option<(f64, f64)>,
+    ) {
+        self.render_layer(buf, state, |ctx| {
+            self.draw_velocity_vector(ctx, state, ground_velocity);
+
+            for (i, &index) in state.selected_objects.iter().enumerate() {
+                let selected = &self.satellites_state.objects[index];
+                let points = &state.trajectories[&index].points;
+
+                // Draw the lines between predicted points
+                let color = self.trajectory_colors[i % self.trajectory_colors.len()];
+                draw_path(ctx, &state.viewport, points, color);
+
+                // Mark the events along the trajectory of the most recently selected object
+                if state.selected_object() == Some(index) {
+                    for event in &state.trajectories[&index].events {
+                        let (lon, lat) = event.position;
+                        ctx.print(lon, lat, event_label(event).fg(event.kind.color()));
+                    }
+                }
+
+                // Highlight the selected satellite
+                let state = &self.satellites_state.states[index];
+                ctx.print(
+                    state.longitude(),
+                    state.latitude(),
+                    self.symbol(selected).light_green().slow_blink(),
+                );
+            }
+
+            if let Some(hovered_object_index) = state
+                .hovered_object
+                .filter(|&index| !state.is_selected(index))
+            {
+                let hovered = &self.satellites_state.objects[hovered_object_index];
+                let state = &self.satellites_state.states[hovered_object_index];
+
+                // Highlight the hovered satellite
+                ctx.print(
+                    state.longitude(),
+                    state.latitude(),
+                    self.symbol(hovered).light_red().reversed(),
+                );
+            }
+        });
+    }
+
+    /// Draws an arrow from the followed object along its ground track, whose length is the
+    /// distance it travels in a time that shrinks as the map is zoomed in.
+    fn draw_velocity_vector(
+        &self,
+        ctx: &mut Context,
+        state: &WorldMapState,
+        ground_velocity: Option<(f64, f64)>,
+    ) {
+        /// Time the arrow spans at the whole-world zoom level, in seconds.
+        const VECTOR_DURATION: f64 = 5.0 * 60.0;
+
+        let (Some(index), Some((lon_rate, lat_rate))) = (
+            state.follow.then(|| state.selected_object()).flatten(),
+            ground_velocity,
+        ) else {
+            return;
+        };
+        let object_state = &self.satellites_state.states[index];
+        let (lon, lat) = (object_state.longitude(), object_state.latitude());
+
+        let duration = VECTOR_DURATION / state.viewport.zoom();
+        let (dx, dy) = (lon_rate * duration, lat_rate * duration);
+        let (end_x, end_y) = (lon + dx, lat + dy);
+        let mut points = vec![(lon, lat), (end_x, end_y)];
+        // Arrowhead barbs, a quarter of the arrow long
+        for angle in [150.0_f64, -150.0] {
+            let (sin, cos) = angle.to_radians().sin_cos();
+            let barb = ((dx * cos - dy * sin) / 4.0, (dx * sin + dy * cos) / 4.0);
+            points.extend([(end_x, end_y), (end_x + barb.0, end_y + barb.1)]);
+        }
+        for line in points.chunks(2) {
+            draw_line(ctx, &state.viewport, line[0], line[1], Color::White);
+        }
+    }
+
+    /// Renders the map, ground stations, trajectories and objects as an image.
+    ///
+    /// Text does not survive rasterization, so objects and ground stations are drawn as dots and
+    /// their labels are left to be drawn over the image.
+    fn render_raster(
+        &self,
+        buf: &mut Buffer,
+        state: &mut WorldMapState,
+        raster: &mut RasterMap,
+        positions: &[(f64, f64)],
+        ground_velocity: Option<(f64, f64)>,
+    ) {
+        let dot_size = raster.dot_size(state.inner_area, &state.viewport);
+        let selected = state
+            .selected_object()
+            .map(|index| &self.satellites_state.states[index]);
+
+        let state = &*state;
+        raster.render(
+            state.inner_area,
+            buf,
+            &state.viewport,
+            &state.selected_objects,
+            state.show_graticule,
+            |ctx| {
+                if state.show_graticule {
+                    draw_graticule(ctx, &state.viewport);
+                }
                 ctx.draw(&Map {
                     color: Color::Gray,
                     resolution: MapResolution::High,
                 });
+                ctx.layer();
+
+                for (index, &(lon, lat)) in positions.iter().enumerate() {
+                    if self.legend_state.is_hidden(index) {
+                        continue;
+                    }
+                    let color = self.legend_state.color(index).unwrap_or(Color::LightRed);
+                    draw_dot(ctx, lon, lat, dot_size, 1, color);
+                }
+                ctx.layer();
 
-                // Draw satellites
-                for object in self.satellites_state.objects.iter() {
-                    let line = if state.selected_object.is_none() {
-                        self.satellit_symbol.clone().light_red()
-                            + format!(" {}", object.name()).white()
+                for station in self.ground_stations {
+                    let has_line_of_sight =
+                        selected.is_some_and(|state| station.has_line_of_sight(state));
+                    let color = if has_line_of_sight {
+                        Color::LightGreen
                     } else {
-                        self.satellit_symbol.clone().red()
-                            + format!(" {}", object.name()).dark_gray()
+                        Color::Cyan
                     };
-                    let state = object.predict(Utc::now()).unwrap();
-                    ctx.print(state.position[0], state.position[1], line);
+                    if let Some(selected) = selected {
+                        let circle_color = if has_line_of_sight {
+                            Color::LightGreen
+                        } else {
+                            Color::Yellow
+                        };
+                        draw_path(
+                            ctx,
+                            &state.viewport,
+                            &station.visibility_circle(selected.altitude()),
+                            circle_color,
+                        );
+                    }
+                    draw_dot(ctx, station.longitude, station.latitude, dot_size, 2, color);
+                }
+
+                for (i, &index) in state.selected_objects.iter().enumerate() {
+                    let color = self.trajectory_colors[i % self.trajectory_colors.len()];
+                    draw_path(
+                        ctx,
+                        &state.viewport,
+                        &state.trajectories[&index].points,
+                        color,
+                    );
+                }
+                self.draw_velocity_vector(ctx, state, ground_velocity);
+                ctx.layer();
+
+                if let Some(measurement) = &state.measurement {
+                    draw_measurement(ctx, &state.viewport, measurement, positions);
+                    for &endpoint in &measurement.endpoints {
+                        let (lon, lat) = endpoint_position(endpoint, positions);
+                        draw_dot(ctx, lon, lat, dot_size, 2, Color::LightYellow);
+                    }
+                }
+                if let Some(index) = state.selected_object() {
+                    for event in &state.trajectories[&index].events {
+                        let (lon, lat) = event.position;
+                        draw_dot(ctx, lon, lat, dot_size, 2, event.kind.color());
+                    }
+                }
+
+                for body in CelestialBody::iter() {
+                    let (lon, lat) = body.sub_point(self.satellites_state.time);
+                    let color = match body {
+                        CelestialBody::Sun => Color::Yellow,
+                        CelestialBody::Moon => Color::White,
+                    };
+                    draw_dot(ctx, lon, lat, dot_size, 3, color);
                 }
-            })
-            .x_bounds([-180.0, 180.0])
-            .y_bounds([-90.0, 90.0]);
 
-        bottom_layer.render(state.inner_area, buf);
+                for &index in &state.selected_objects {
+                    let (lon, lat) = positions[index];
+                    draw_dot(ctx, lon, lat, dot_size, 2, Color::LightGreen);
+                }
+                if let Some(index) = state.hovered_object {
+                    let (lon, lat) = positions[index];
+                    draw_dot(ctx, lon, lat, dot_size, 2, Color::LightRed);
+                }
+            },
+        );
     }
 
-    fn render_top_layer(&self, buf: &mut Buffer, state: &mut WorldMapState) {
-        let top_layer = Canvas::default()
-            .paint(|ctx| {
-                if let Some(selected_object_index) = state.selected_object {
-                    let selected = &self.satellites_state.objects[selected_object_index];
-                    let state = selected.predict(Utc::now()).unwrap();
+    /// Returns the results of the measurement, or a prompt for its missing ends.
+    fn measurement_readout(
+        &self,
+        state: &WorldMapState,
+        positions: &[(f64, f64)],
+    ) -> Option<String> {
+        let measurement = state.measurement.as_ref()?;
+        let [from, to] = measurement.endpoints[..] else {
+            return Some(match measurement.endpoints.len() {
+                0 => "Measure: click the first point or object".to_string(),
+                _ => "Measure: click the second point or object".to_string(),
+            });
+        };
 
-                    // Calculate future positions along the trajectory
-                    let mut points = Vec::new();
-                    for minutes in 1..selected.orbital_period().num_minutes() {
-                        let time = Utc::now() + Duration::minutes(minutes);
-                        let state = selected.predict(time).unwrap();
-                        points.push((state.position[0], state.position[1]));
-                    }
+        let (start, end) = (
+            endpoint_position(from, positions),
+            endpoint_position(to, positions),
+        );
+        let mut readout = format!(
+            "{:.1} km, bearing {:03.0}°",
+            geodesy::great_circle_distance(start, end),
+            geodesy::initial_bearing(start, end)
+        );
 
-                    // Draw the lines between predicted points
-                    for window in points.windows(2) {
-                        let (x1, y1) = window[0];
-                        let (x2, y2) = window[1];
-                        // Handle trajectory crossing the international date line
-                        if (x1 - x2).abs() >= 180.0 {
-                            let x_edge = if x1 > 0.0 { 180.0 } else { -180.0 };
-                            ctx.draw(&Line::new(x1, y1, x_edge, y2, self.trajectory_color));
-                            ctx.draw(&Line::new(-x_edge, y1, x2, y2, self.trajectory_color));
-                            continue;
-                        }
-                        if (y1 - y2).abs() >= 90.0 {
-                            // TEMPSAT 1 (1512), CALSPHERE 4A (1520)
-                            continue;
-                        }
-                        ctx.draw(&Line::new(x1, y1, x2, y2, self.trajectory_color));
-                    }
+        if let (Endpoint::Object { index: a, .. }, Endpoint::Object { index: b, .. }) = (from, to) {
+            let time = self.satellites_state.time;
+            let objects = &self.satellites_state.objects;
+            let states = &self.satellites_state.states;
+            let range = distance(&states[a], &states[b]);
+            // Differentiate the range numerically
+            let later = time + chrono::Duration::seconds(1);
+            if let (Ok(later_a), Ok(later_b)) =
+                (objects[a].predict(later), objects[b].predict(later))
+            {
+                let range_rate = distance(&later_a, &later_b) - range;
+                readout += &format!(
+                    ", range {:.1} km, range rate {:+.3} km/s",
+                    range, range_rate
+                );
+            }
+        }
+        Some(readout)
+    }
 
-                    // Highlight the selected satellite
-                    ctx.print(
-                        state.position[0],
-                        state.position[1],
-                        self.satellit_symbol.clone().light_green().slow_blink()
-                            + format!(" {}", selected.name()).white(),
-                    );
-                } else if let Some(hovered_object_index) = state.hovered_object {
-                    let hovered = &self.satellites_state.objects[hovered_object_index];
-                    let state = hovered.predict(Utc::now()).unwrap();
-
-                    // Highlight the hovered satellite
-                    ctx.print(
-                        state.position[0],
-                        state.position[1],
-                        self.satellit_symbol.clone().light_red().reversed()
-                            + " ".into()
-                            + hovered.name().clone().white().reversed(),
-                    );
+    /// Renders the great circle between the ends of the measurement.
+    fn render_measurement(
+        &self,
+        buf: &mut Buffer,
+        state: &WorldMapState,
+        positions: &[(f64, f64)],
+    ) {
+        let Some(measurement) = &state.measurement else {
+            return;
+        };
+        self.render_layer(buf, state, |ctx| {
+            draw_measurement(ctx, &state.viewport, measurement, positions);
+            for &endpoint in &measurement.endpoints {
+                if let Endpoint::Location((lon, lat)) = endpoint {
+                    ctx.print(lon, lat, "×".light_yellow().bold());
                 }
+            }
+        });
+    }
+
+    /// Renders a popup with details of the hovered object next to the mouse cursor.
+    fn render_tooltip(&self, buf: &mut Buffer, state: &mut WorldMapState) {
+        /// How far ahead the next pass is searched for.
+        const PASS_WINDOW: chrono::Duration = chrono::Duration::hours(24);
+
+        let (Some(index), Some((lon, lat))) = (state.hovered_object, state.cursor) else {
+            return;
+        };
+        let area = state.inner_area;
+        let Some((x, y)) = state.viewport.lon_lat_to_area(lon, lat, area) else {
+            return;
+        };
+        let object = &self.satellites_state.objects[index];
+        let object_state = &self.satellites_state.states[index];
+
+        let mut rows = vec![
+            ("NORAD ID", object.norad_id().to_string()),
+            ("Altitude", format!("{:.1} km", object_state.altitude())),
+            ("Speed", format!("{:.2} km/s", object_state.speed())),
+            ("Group", object.group().to_string()),
+        ];
+        if let Some(observer) = self.observer {
+            // Predicting passes is expensive, so only repeat it once a minute
+            let time = self.satellites_state.time;
+            let minute = time.duration_trunc(chrono::Duration::minutes(1)).unwrap();
+            let next_pass = match state.next_pass {
+                Some((norad_id, predicted_at, next_pass))
+                    if norad_id == object.norad_id() && predicted_at == minute =>
+                {
+                    next_pass
+                }
+                _ => {
+                    let next_pass = observer.next_pass(object, time, PASS_WINDOW);
+                    state.next_pass = Some((object.norad_id(), minute, next_pass));
+                    next_pass
+                }
+            };
+            let next_pass = match next_pass {
+                Some(start) if start <= time => "now".to_string(),
+                Some(start) => start.format("%H:%M:%S").to_string(),
+                None => "none in 24 h".to_string(),
+            };
+            rows.push(("Next pass", next_pass));
+        }
+
+        let key_width = rows.iter().map(|(key, _)| key.width()).max().unwrap_or(0);
+        let lines: Vec<ratatui::text::Line> = rows
+            .into_iter()
+            .map(|(key, value)| {
+                ratatui::text::Line::from(vec![format!("{:key_width$} ", key).bold(), value.into()])
             })
-            .x_bounds([-180.0, 180.0])
-            .y_bounds([-90.0, 90.0]);
+            .collect();
+        let width = lines
+            .iter()
+            .map(|line| line.width())
+            .chain([object.name().width()])
+            .max()
+            .unwrap_or(0) as u16
+            + 2;
+        let height = lines.len() as u16 + 2;
+        if width > area.width || height > area.height {
+            return;
+        }
+
+        // Place the popup below and to the right of the cursor, flipping it to stay in the area
+        let popup_x = if x + 2 + width <= area.width {
+            x + 2
+        } else {
+            x.saturating_sub(width + 1)
+        };
+        let popup_y = if y + 1 + height <= area.height {
+            y + 1
+        } else {
+            y.saturating_sub(height)
+        };
+        let popup = Rect::new(area.x + popup_x, area.y + popup_y, width, height);
+
+        Clear.render(popup, buf);
+        Paragraph::new(lines)
+            .block(Block::bordered().title(object.name().clone().light_red()))
+            .render(popup, buf);
+    }
+
+    /// Returns the symbol of the object, which depends on its type.
+    fn symbol(&self, object: &Object) -> String {
+        match object.object_type() {
+            ObjectType::RocketBody => "#".to_string(),
+            ObjectType::Debris => "·".to_string(),
+            ObjectType::Payload | ObjectType::Unknown => self.satellit_symbol.clone(),
+        }
+    }
+
+    /// Renders object names next to their symbols.
+    ///
+    /// Labels never overlap each other. The labels of the selected, hovered and watch-listed
+    /// objects are placed first and always shown, the remaining labels are hidden where objects
+    /// are too dense on screen or no free space is left next to the symbol.
+    fn render_labels(&self, buf: &mut Buffer, state: &WorldMapState, positions: &[(f64, f64)]) {
+        /// Size of the screen region in which object density is measured, in cells.
+        const DENSITY_BIN_SIZE: (u16, u16) = (12, 4);
+        /// Maximum number of objects in a region for their labels to be shown.
+        const DENSITY_THRESHOLD: usize = 3;
+
+        let area = state.inner_area;
+        if area.is_empty() {
+            return;
+        }
+
+        let viewport = &state.viewport;
+        let cells: Vec<Option<(u16, u16)>> = positions
+            .iter()
+            .map(|&(lon, lat)| viewport.lon_lat_to_area(lon, lat, area))
+            .collect();
+
+        let objects = &self.satellites_state.objects;
+        let is_shown = |index: usize| {
+            cells[index].is_some()
+                && (!self.legend_state.is_hidden(index) || state.is_selected(index))
+        };
+
+        let mut placer = LabelPlacer::new(area);
+        for (index, cell) in cells.iter().enumerate() {
+            if let Some((x, y)) = cell.filter(|_| is_shown(index)) {
+                placer.reserve(x, y, self.symbol(&objects[index]).width() as u16);
+            }
+        }
+        for station in self.ground_stations {
+            if let Some((x, y)) =
+                viewport.lon_lat_to_area(station.longitude, station.latitude, area)
+            {
+                placer.reserve(x, y, station.name.width() as u16 + 2);
+            }
+        }
+        for body in CelestialBody::iter() {
+            let (lon, lat) = body.sub_point(self.satellites_state.time);
+            if let Some((x, y)) = viewport.lon_lat_to_area(lon, lat, area) {
+                placer.reserve(x, y, body.symbol().width() as u16);
+            }
+        }
+        if let Some(index) = state.selected_object() {
+            for event in &state.trajectories[&index].events {
+                let (lon, lat) = event.position;
+                if let Some((x, y)) = viewport.lon_lat_to_area(lon, lat, area) {
+                    placer.reserve(x, y, event_label(event).width() as u16);
+                }
+            }
+        }
+
+        let mut density = HashMap::new();
+        for (x, y) in cells
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell)| cell.filter(|_| is_shown(index)))
+        {
+            *density
+                .entry((x / DENSITY_BIN_SIZE.0, y / DENSITY_BIN_SIZE.1))
+                .or_insert(0) += 1;
+        }
+
+        let is_prioritized = |index: usize| {
+            state.is_selected(index)
+                || state.hovered_object == Some(index)
+                || self.watch_list.contains(&objects[index].norad_id())
+        };
+        let (prioritized, others): (Vec<usize>, Vec<usize>) = (0..objects.len())
+            .filter(|&index| is_shown(index))
+            .partition(|&index| is_prioritized(index));
 
-        top_layer.render(state.inner_area, buf);
+        // Individual labels are meaningless in the heatmap
+        let others = match state.mode {
+            MapMode::Markers => others,
+            MapMode::Heatmap => Vec::new(),
+        };
+
+        for index in prioritized.into_iter().chain(others) {
+            let Some((x, y)) = cells[index] else {
+                continue;
+            };
+            let force = is_prioritized(index);
+            if !force
+                && density[&(x / DENSITY_BIN_SIZE.0, y / DENSITY_BIN_SIZE.1)] > DENSITY_THRESHOLD
+            {
+                continue;
+            }
+
+            let name = objects[index].name();
+            let Some((label_x, label_y)) = placer.place(x, y, name.width() as u16, force) else {
+                continue;
+            };
+
+            let selection_order = state.selected_objects.iter().position(|&i| i == index);
+            let style = if let Some(i) = selection_order {
+                if state.selected_objects.len() > 1 {
+                    Style::new().fg(self.trajectory_colors[i % self.trajectory_colors.len()])
+                } else {
+                    Style::new().white()
+                }
+            } else if state.hovered_object == Some(index) {
+                Style::new().white().reversed()
+            } else if self.watch_list.contains(&objects[index].norad_id()) {
+                Style::new().yellow()
+            } else if state.selected_objects.is_empty() {
+                Style::new().white()
+            } else {
+                Style::new().dark_gray()
+            };
+            buf.set_stringn(
+                area.x + label_x,
+                area.y + label_y,
+                name,
+                (area.width - label_x) as usize,
+                style,
+            );
+        }
     }
 }
 
@@ -128,9 +822,89 @@ impl StatefulWidget for WorldMap<'_> {
     type State = WorldMapState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        self.render_block(area, buf, state);
-        self.render_bottom_layer(buf, state);
-        self.render_top_layer(buf, state);
+        // The objects may have been reloaded since the ends were picked
+        if let Some(measurement) = &mut state.measurement {
+            measurement.resolve(&self.satellites_state.objects);
+        }
+
+        let positions: Vec<(f64, f64)> = self
+            .satellites_state
+            .states
+            .iter()
+            .map(|state| (state.longitude(), state.latitude()))
+            .collect();
+
+        // Keep the followed object centred
+        let followed = state.follow.then(|| state.selected_object()).flatten();
+        if let Some(index) = followed {
+            let (lon, lat) = positions[index];
+            state.viewport.center_on(lon, lat);
+        }
+        let ground_velocity = followed.and_then(|index| {
+            ground_velocity(
+                &self.satellites_state.objects[index],
+                self.satellites_state.time,
+            )
+        });
+
+        self.render_block(area, buf, state, ground_velocity, &positions);
+
+        // Index the visible objects for picking with the mouse. The heatmap draws no objects, so
+        // none can be picked.
+        state.spatial_index = SpatialIndex::new(state.viewport.wrap_width(state.inner_area));
+        match state.mode {
+            MapMode::Markers => {
+                for (index, &(lon, lat)) in positions.iter().enumerate() {
+                    if self.legend_state.is_hidden(index) {
+                        continue;
+                    }
+                    if let Some(position) =
+                        state
+                            .viewport
+                            .lon_lat_to_area_fractional(lon, lat, state.inner_area)
+                    {
+                        state.spatial_index.insert(index, position);
+                    }
+                }
+            }
+            MapMode::Heatmap => state.hovered_object = None,
+        }
+
+        self.update_trajectories(state);
+
+        // The heatmap shades cells, which only the canvas can do
+        if state.mode == MapMode::Markers {
+            if let Some(mut raster) = state.raster.take() {
+                self.render_raster(buf, state, &mut raster, &positions, ground_velocity);
+                // Text does not survive rasterization, so it is drawn over the image
+                let mut overlay = RasterMap::overlay_buffer(area);
+                if state.show_graticule {
+                    self.render_layer(&mut overlay, state, |ctx| {
+                        draw_graticule(ctx, &state.viewport)
+                    });
+                }
+                self.render_ground_stations(&mut overlay, state);
+                self.render_top_layer(&mut overlay, state, ground_velocity);
+                self.render_measurement(&mut overlay, state, &positions);
+                self.render_labels(&mut overlay, state, &positions);
+                self.render_tooltip(&mut overlay, state);
+                raster.render_overlay(state.inner_area, buf, &overlay);
+                state.raster = Some(raster);
+                return;
+            }
+        }
+
+        self.render_bottom_layer(buf, state, &positions);
+        self.render_ground_stations(buf, state);
+        self.render_celestial_bodies(buf, state);
+        self.render_top_layer(buf, state, ground_velocity);
+        self.render_measurement(buf, state, &positions);
+        // Shade after the canvases, which reset the background
+        if state.mode == MapMode::Heatmap {
+            self.render_heatmap(buf, state);
+        }
+        self.render_labels(buf, state, &positions);
+        self.render_tooltip(buf, state);
     }
 }
 
@@ -138,19 +912,52 @@ pub async fn handle_mouse_events(event: MouseEvent, app: &mut App) -> Result<()>
     let inner_area = app.world_map_state.inner_area;
     if !inner_area.contains(Position::new(event.column, event.row)) {
         app.world_map_state.hovered_object = None;
+        app.world_map_state.cursor = None;
+        app.world_map_state.cursor_location = None;
         return Ok(());
     }
 
     // Convert window coordinates to area coordinates
     let mouse = Position::new(event.column - inner_area.x, event.row - inner_area.y);
 
+    // Look up the place under the cursor
+    let (lon, lat) = app
+        .world_map_state
+        .viewport
+        .area_to_lon_lat(mouse.x, mouse.y, inner_area);
+    if app.world_map_state.cursor != Some((lon, lat)) {
+        let location = app.geolocator.locate(lon, lat).describe(&app.config.units);
+        app.world_map_state.cursor = Some((lon, lat));
+        app.world_map_state.cursor_location = Some(location);
+    }
+
     if let MouseEventKind::Down(buttom) = event.kind {
         match buttom {
+            MouseButton::Left if app.world_map_state.measurement.is_some() => {
+                // Measure to the clicked object, or to the clicked point if there is none
+                let endpoint = match get_nearest_object(app, mouse.x, mouse.y) {
+                    Some(index) => Endpoint::Object {
+                        norad_id: app.satellites_state.objects[index].norad_id(),
+                        index,
+                    },
+                    None => Endpoint::Location((lon, lat)),
+                };
+                if let Some(measurement) = &mut app.world_map_state.measurement {
+                    measurement.add(endpoint);
+                }
+            }
             MouseButton::Left => {
-                app.world_map_state.selected_object = get_nearest_object(app, mouse.x, mouse.y);
+                if let Some(index) = get_nearest_object(app, mouse.x, mouse.y) {
+                    if event.modifiers.contains(KeyModifiers::CONTROL) {
+                        // Add to or remove from the selection on `Ctrl`-click
+                        app.world_map_state.toggle_selection(index);
+                    } else {
+                        app.world_map_state.selected_objects = vec![index];
+                    }
+                }
             }
             MouseButton::Right => {
-                app.world_map_state.selected_object = None;
+                app.world_map_state.selected_objects.clear();
             }
             _ => {}
         }
@@ -160,40 +967,218 @@ pub async fn handle_mouse_events(event: MouseEvent, app: &mut App) -> Result<()>
     Ok(())
 }
 
-/// Get the index of the nearest object to the given area coordinates
-fn get_nearest_object(app: &mut App, x: u16, y: u16) -> Option<usize> {
-    app.satellites_state
-        .objects
-        .iter()
-        .enumerate()
-        .min_by_key(|(_, obj)| {
-            let state = obj.predict(Utc::now()).unwrap();
-            let (lon, lat) = area_to_lon_lat(x, y, app.world_map_state.inner_area);
-            let dx = state.longitude() - lon;
-            let dy = state.latitude() - lat;
-            ((dx * dx + dy * dy) * 1000.0) as i32
+pub async fn handle_action(action: Action, app: &mut App) -> Result<()> {
+    /// Fraction of the viewport moved by a pan.
+    const PAN_STEP: f64 = 0.125;
+
+    let (dx, dy) = match action {
+        Action::Left => (-PAN_STEP, 0.0),
+        Action::Right => (PAN_STEP, 0.0),
+        Action::Up => (0.0, PAN_STEP),
+        Action::Down => (0.0, -PAN_STEP),
+        Action::NextObject => {
+            cycle_selection(app, false);
+            return Ok(());
+        }
+        Action::PreviousObject => {
+            cycle_selection(app, true);
+            return Ok(());
+        }
+        _ => return Ok(()),
+    };
+    app.world_map_state.viewport.pan(dx, dy);
+    app.world_map_state.follow = false;
+
+    Ok(())
+}
+
+/// Selects the visible object after the most recently selected one, or before it if `reverse`.
+fn cycle_selection(app: &mut App, reverse: bool) {
+    let state = &app.world_map_state;
+    let is_visible = |index: usize| {
+        let object_state = &app.satellites_state.states[index];
+        !app.legend_state.is_hidden(index)
+            && state
+                .viewport
+                .lon_lat_to_area(
+                    object_state.longitude(),
+                    object_state.latitude(),
+                    state.inner_area,
+                )
+                .is_some()
+    };
+
+    let count = app.satellites_state.objects.len();
+    let selected = state.selected_object();
+    let next = (1..=count)
+        .map(|offset| match (selected, reverse) {
+            (Some(index), false) => (index + offset) % count,
+            (Some(index), true) => (index + count - offset) % count,
+            (None, false) => offset - 1,
+            (None, true) => count - offset,
         })
-        .map(|(index, _)| index)
+        .find(|&index| is_visible(index));
+    if let Some(index) = next {
+        app.world_map_state.selected_objects = vec![index];
+    }
+}
+
+/// Draw the great circle between the ends of the measurement
+fn draw_measurement(
+    ctx: &mut Context,
+    viewport: &Viewport,
+    measurement: &Measurement,
+    positions: &[(f64, f64)],
+) {
+    /// Number of straight segments approximating the great circle.
+    const SEGMENTS: usize = 64;
+
+    if let [from, to] = measurement.endpoints[..] {
+        let path = geodesy::great_circle_path(
+            endpoint_position(from, positions),
+            endpoint_position(to, positions),
+            SEGMENTS,
+        );
+        draw_path(ctx, viewport, &path, Color::LightYellow);
+    }
 }
 
-/// Convert area coordinates to lon/lat coordinates
-fn area_to_lon_lat(x: u16, y: u16, area: Rect) -> (f64, f64) {
-    debug_assert!(x < area.width && y < area.height);
+/// Returns the current longitude and latitude of the end of a measurement
+fn endpoint_position(endpoint: Endpoint, positions: &[(f64, f64)]) -> (f64, f64) {
+    match endpoint {
+        Endpoint::Location(position) => position,
+        Endpoint::Object { index, .. } => positions[index],
+    }
+}
+
+/// Returns the straight-line distance between two objects in km
+fn distance(a: &State, b: &State) -> f64 {
+    let a = lat_lon_alt_to_ecef([a.latitude(), a.longitude(), a.altitude()]);
+    let b = lat_lon_alt_to_ecef([b.latitude(), b.longitude(), b.altitude()]);
+    (0..3).map(|i| (a[i] - b[i]).powi(2)).sum::<f64>().sqrt()
+}
+
+/// Returns the symbol and time of the event
+fn event_label(event: &OrbitEvent) -> String {
+    format!("{} {}", event.kind.symbol(), event.time.format("%H:%M"))
+}
+
+/// Draw lines of longitude and latitude with their labels
+///
+/// Lines are closer together the more the viewport is zoomed in.
+fn draw_graticule(ctx: &mut Context, viewport: &Viewport) {
+    const COLOR: Color = Color::DarkGray;
+
+    let step = match viewport.zoom() {
+        ..2.0 => 30,
+        ..4.0 => 15,
+        ..8.0 => 10,
+        ..16.0 => 5,
+        _ => 2,
+    };
+    for lon in (-180..=180).step_by(step) {
+        let lon = lon as f64;
+        draw_line(ctx, viewport, (lon, -90.0), (lon, 90.0), COLOR);
+    }
+    for lat in (-90..=90).step_by(step) {
+        let lat = lat as f64;
+        draw_line(ctx, viewport, (-180.0, lat), (180.0, lat), COLOR);
+    }
+    ctx.layer();
+
+    // Label the meridians along the bottom edge and the parallels along the left edge
+    let [left, _] = viewport.x_bounds();
+    let [bottom, _] = viewport.y_bounds();
+    for lon in (-180 + step as i32..180).step_by(step) {
+        let label = match lon {
+            0 => "0°".to_string(),
+            ..0 => format!("{}°W", -lon),
+            _ => format!("{}°E", lon),
+        };
+        ctx.print(lon as f64, bottom, label.dark_gray());
+    }
+    for lat in (-90 + step as i32..90).step_by(step) {
+        let label = match lat {
+            0 => "0°".to_string(),
+            ..0 => format!("{}°S", -lat),
+            _ => format!("{}°N", lat),
+        };
+        ctx.print(left, lat as f64, label.dark_gray());
+    }
+}
+
+/// Draw a path through the given lon/lat points
+fn draw_path(ctx: &mut Context, viewport: &Viewport, points: &[(f64, f64)], color: Color) {
+    for window in points.windows(2) {
+        let (x1, y1) = window[0];
+        let (x2, y2) = window[1];
+        // Handle path crossing the international date line
+        if (x1 - x2).abs() >= 180.0 {
+            let x_edge = if x1 > 0.0 { 180.0 } else { -180.0 };
+            draw_line(ctx, viewport, (x1, y1), (x_edge, y2), color);
+            draw_line(ctx, viewport, (-x_edge, y1), (x2, y2), color);
+            continue;
+        }
+        if (y1 - y2).abs() >= 90.0 {
+            // TEMPSAT 1 (1512), CALSPHERE 4A (1520)
+            continue;
+        }
+        draw_line(ctx, viewport, (x1, y1), (x2, y2), color);
+    }
+}
+
+/// Draw the part of the line between the given lon/lat points inside the viewport
+fn draw_line(
+    ctx: &mut Context,
+    viewport: &Viewport,
+    start: (f64, f64),
+    end: (f64, f64),
+    color: Color,
+) {
+    for [(x1, y1), (x2, y2)] in viewport.clip_line(start, end) {
+        ctx.draw(&Line::new(x1, y1, x2, y2, color));
+    }
+}
+
+/// Draw a square dot of `2 * radius + 1` canvas dots centred on the given lon/lat point
+fn draw_dot(
+    ctx: &mut Context,
+    lon: f64,
+    lat: f64,
+    (dot_width, dot_height): (f64, f64),
+    radius: i32,
+    color: Color,
+) {
+    let coords: Vec<(f64, f64)> = (-radius..=radius)
+        .flat_map(|dx| (-radius..=radius).map(move |dy| (dx, dy)))
+        .map(|(dx, dy)| (lon + dx as f64 * dot_width, lat + dy as f64 * dot_height))
+        .collect();
+    ctx.draw(&Points {
+        coords: &coords,
+        color,
+    });
+}
+
+/// Get the index of the nearest object to the given area coordinates
+///
+/// Only objects within a few cells of the given coordinates are considered.
+fn get_nearest_object(app: &mut App, x: u16, y: u16) -> Option<usize> {
+    /// Maximum distance between the mouse cursor and a picked object in cells.
+    const PICK_RADIUS: f64 = 3.0;
 
-    let normalized_x = (x + 1) as f64 / area.width as f64;
-    let normalized_y = (y + 1) as f64 / area.height as f64;
-    let lon = -180.0 + normalized_x * 360.0;
-    let lat = 90.0 - normalized_y * 180.0;
-    (lon, lat)
+    app.world_map_state.spatial_index.nearest(x, y, PICK_RADIUS)
 }
 
-#[allow(dead_code)]
-/// Convert lon/lat coordinates to area coordinates
-fn lon_lat_to_area(lon: f64, lat: f64, area: Rect) -> (u16, u16) {
-    debug_assert!((-180.0..=180.0).contains(&lon));
-    debug_assert!((-90.0..=90.0).contains(&lat));
+/// Returns the rate of change of the longitude and latitude of the object in deg/s.
+fn ground_velocity(object: &Object, time: DateTime<Utc>) -> Option<(f64, f64)> {
+    /// Time between the positions the velocity is derived from, in seconds.
+    const TIME_STEP: f64 = 10.0;
 
-    let x = ((lon + 180.0) * area.width as f64 / 360.0) - 1.0;
-    let y = ((90.0 - lat) * area.height as f64 / 180.0) - 1.0;
-    (x.round() as u16, y.round() as u16)
+    let now = object.predict(time).ok()?;
+    let later = object
+        .predict(time + chrono::Duration::milliseconds((TIME_STEP * 1000.0) as i64))
+        .ok()?;
+    let lon_delta = (later.longitude() - now.longitude() + 540.0).rem_euclid(360.0) - 180.0;
+    let lat_delta = later.latitude() - now.latitude();
+    Some((lon_delta / TIME_STEP, lat_delta / TIME_STEP))
 }
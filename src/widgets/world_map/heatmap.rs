@@ -0,0 +1,105 @@
+use ratatui::{buffer::Buffer, layout::Rect, style::palette::tailwind};
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+/// Shades from the lowest to the highest density.
+const SHADES: [ratatui::style::Color; 6] = [
+    tailwind::INDIGO.c900,
+    tailwind::VIOLET.c700,
+    tailwind::FUCHSIA.c600,
+    tailwind::ROSE.c500,
+    tailwind::ORANGE.c400,
+    tailwind::YELLOW.c300,
+];
+
+/// Number of objects in each bin of screen cells.
+pub struct Heatmap {
+    area: Rect,
+    counts: Vec<usize>,
+}
+
+impl Heatmap {
+    /// Width and height of a bin in cells, about square on screen.
+    const BIN_SIZE: (u16, u16) = (2, 1);
+
+    /// Constructs a new, empty instance of [`Heatmap`] covering the given area.
+    pub fn new(area: Rect) -> Self {
+        let columns = area.width.div_ceil(Self::BIN_SIZE.0) as usize;
+        let rows = area.height.div_ceil(Self::BIN_SIZE.1) as usize;
+        Self {
+            area,
+            counts: vec![0; columns * rows],
+        }
+    }
+
+    /// Counts an object in the cell at the given position relative to the area.
+    pub fn add(&mut self, x: u16, y: u16) {
+        if x < self.area.width && y < self.area.height {
+            let index = self.index(x, y);
+            self.counts[index] += 1;
+        }
+    }
+
+    /// Shades the background of each non-empty bin by its count on a logarithmic scale.
+    pub fn render(&self, buf: &mut Buffer) {
+        let max = self.counts.iter().copied().max().unwrap_or(0);
+        if max == 0 {
+            return;
+        }
+
+        for y in 0..self.area.height {
+            for x in 0..self.area.width {
+                let count = self.counts[self.index(x, y)];
+                if count == 0 {
+                    continue;
+                }
+                let level = ((count as f64).ln_1p() / (max as f64).ln_1p()
+                    * (SHADES.len() - 1) as f64)
+                    .round() as usize;
+                buf[(self.area.x + x, self.area.y + y)].set_bg(SHADES[level]);
+            }
+        }
+    }
+
+    fn index(&self, x: u16, y: u16) -> usize {
+        let columns = self.area.width.div_ceil(Self::BIN_SIZE.0) as usize;
+        (y / Self::BIN_SIZE.1) as usize * columns + (x / Self::BIN_SIZE.0) as usize
+    }
+}
+
+/// Range of altitudes of the objects included in the heatmap.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Display, EnumIter)]
+pub enum AltitudeBand {
+    #[default]
+    #[strum(to_string = "all altitudes")]
+    All,
+    #[strum(to_string = "below 2,000 km")]
+    Low,
+    #[strum(to_string = "2,000-35,586 km")]
+    Medium,
+    #[strum(to_string = "35,586-35,986 km")]
+    Geostationary,
+    #[strum(to_string = "above 35,986 km")]
+    High,
+}
+
+impl AltitudeBand {
+    /// Returns `true` if the altitude in km is within the band.
+    pub fn contains(self, altitude: f64) -> bool {
+        match self {
+            Self::All => true,
+            Self::Low => altitude < 2000.0,
+            Self::Medium => (2000.0..35586.0).contains(&altitude),
+            Self::Geostationary => (35586.0..35986.0).contains(&altitude),
+            Self::High => altitude >= 35986.0,
+        }
+    }
+
+    /// Returns the band after this one, wrapping around.
+    pub fn next(self) -> Self {
+        Self::iter()
+            .cycle()
+            .skip_while(|band| *band != self)
+            .nth(1)
+            .unwrap()
+    }
+}
@@ -0,0 +1,69 @@
+use ratatui::layout::Rect;
+
+/// Places labels on the map without overlapping each other.
+pub struct LabelPlacer {
+    area: Rect,
+    occupied: Vec<bool>,
+}
+
+impl LabelPlacer {
+    /// Constructs a new instance of [`LabelPlacer`] covering the given area.
+    pub fn new(area: Rect) -> Self {
+        Self {
+            area,
+            occupied: vec![false; area.width as usize * area.height as usize],
+        }
+    }
+
+    /// Marks the cells in the given span as occupied.
+    ///
+    /// Coordinates are relative to the area.
+    pub fn reserve(&mut self, x: u16, y: u16, width: u16) {
+        if y >= self.area.height {
+            return;
+        }
+        for x in x..x.saturating_add(width).min(self.area.width) {
+            let index = self.index(x, y);
+            self.occupied[index] = true;
+        }
+    }
+
+    /// Finds a free span of the given width next to the symbol at the given cell and reserves it.
+    ///
+    /// Candidates are tried in order: right, left, above and below the symbol.
+    /// If all of them are occupied and `force` is `true`, the label is placed to the right anyway.
+    ///
+    /// Returns the position of the label relative to the area.
+    pub fn place(&mut self, x: u16, y: u16, width: u16, force: bool) -> Option<(u16, u16)> {
+        let candidates = [
+            (x.checked_add(2), Some(y)),
+            (x.checked_sub(width + 1), Some(y)),
+            (Some(x), y.checked_sub(1)),
+            (Some(x), y.checked_add(1)),
+        ];
+        let position = candidates
+            .into_iter()
+            .filter_map(|(x, y)| Some((x?, y?)))
+            .find(|&(x, y)| self.is_free(x, y, width))
+            .or(force.then(|| {
+                (
+                    x.saturating_add(2).min(self.area.width.saturating_sub(1)),
+                    y,
+                )
+            }))?;
+
+        self.reserve(position.0, position.1, width);
+        Some(position)
+    }
+
+    fn is_free(&self, x: u16, y: u16, width: u16) -> bool {
+        if y >= self.area.height || x.saturating_add(width) > self.area.width {
+            return false;
+        }
+        (x..x + width).all(|x| !self.occupied[self.index(x, y)])
+    }
+
+    fn index(&self, x: u16, y: u16) -> usize {
+        y as usize * self.area.width as usize + x as usize
+    }
+}
@@ -0,0 +1,55 @@
+use crate::object::Object;
+
+/// One end of a measurement.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Endpoint {
+    /// A fixed point on the surface, as longitude and latitude in deg.
+    Location((f64, f64)),
+    /// An object, which moves with it.
+    Object {
+        norad_id: u64,
+        /// Index of the object, resolved from the NORAD ID each frame as the objects are
+        /// reloaded.
+        index: usize,
+    },
+}
+
+/// Distance and bearing between two clicked points or objects.
+#[derive(Clone, Default, Debug)]
+pub struct Measurement {
+    /// The ends of the measurement, in the order they were clicked.
+    pub endpoints: Vec<Endpoint>,
+}
+
+impl Measurement {
+    /// Adds an end, starting a new measurement if both ends are already set.
+    pub fn add(&mut self, endpoint: Endpoint) {
+        if self.endpoints.len() == 2 {
+            self.endpoints.clear();
+        }
+        self.endpoints.push(endpoint);
+    }
+
+    /// Updates the indices of the objects at the ends, removing the ends whose object is no
+    /// longer loaded.
+    pub fn resolve(&mut self, objects: &[Object]) {
+        self.endpoints.retain_mut(|endpoint| {
+            let Endpoint::Object { norad_id, index } = endpoint else {
+                return true;
+            };
+            if objects
+                .get(*index)
+                .is_some_and(|o| o.norad_id() == *norad_id)
+            {
+                return true;
+            }
+            match objects.iter().position(|o| o.norad_id() == *norad_id) {
+                Some(position) => {
+                    *index = position;
+                    true
+                }
+                None => false,
+            }
+        });
+    }
+}
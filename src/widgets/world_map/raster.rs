@@ -0,0 +1,269 @@
+use std::time::{Duration, Instant};
+
+use image::{DynamicImage, Rgba, RgbaImage};
+use ratatui::{
+    buffer::{Buffer, Cell},
+    layout::{Position, Rect},
+    style::Color,
+    symbols::Marker,
+    widgets::{
+        canvas::{Canvas, Context},
+        StatefulWidget, Widget,
+    },
+};
+use ratatui_image::{picker::Picker, protocol::StatefulProtocol, Resize, StatefulImage};
+
+use super::viewport::Viewport;
+
+/// Renders the world map as an image through a terminal graphics protocol (Sixel, Kitty, iTerm2).
+///
+/// The map is painted on a braille canvas in an offscreen buffer much larger than the screen
+/// area, whose dots are then converted to pixels. Text is drawn over the image.
+pub struct RasterMap {
+    picker: Picker,
+    protocol: Option<StatefulProtocol>,
+    /// Area, selection, graticule and viewport the current image was painted for.
+    key: Option<(Rect, Vec<usize>, bool, Viewport)>,
+    last_update: Instant,
+    /// Cells of the text drawn over the image in the last frame.
+    overlay: Vec<Position>,
+    /// Whether the image was repainted since the overlay was last drawn.
+    repainted: bool,
+    /// Flipped whenever the image has to be sent to the terminal again, see
+    /// [`Self::render_overlay`].
+    generation: bool,
+}
+
+impl RasterMap {
+    /// Minimum time between repaints of an unchanged scene, as encoding is expensive.
+    const UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+    /// Width and height of a canvas dot in pixels.
+    const DOT_SIZE: u32 = 2;
+    /// Symbol of the overlay cells through which the image shows.
+    const TRANSPARENT: &'static str = "\0";
+
+    /// Constructs a new instance of [`RasterMap`] drawing with the given picker.
+    pub fn new(picker: Picker) -> Self {
+        Self {
+            picker,
+            protocol: None,
+            key: None,
+            last_update: Instant::now(),
+            overlay: Vec::new(),
+            repainted: false,
+            generation: false,
+        }
+    }
+
+    /// Returns an empty overlay for [`Self::render_overlay`] covering the area.
+    pub fn overlay_buffer(area: Rect) -> Buffer {
+        Buffer::filled(area, Cell::new(Self::TRANSPARENT))
+    }
+
+    /// Returns the width and height of a canvas dot in degrees of longitude and latitude.
+    pub fn dot_size(&self, area: Rect, viewport: &Viewport) -> (f64, f64) {
+        let (width, height) = self.canvas_size(area);
+        let [left, right] = viewport.x_bounds();
+        let [bottom, top] = viewport.y_bounds();
+        (
+            (right - left) / (width * 2) as f64,
+            (top - bottom) / (height * 4) as f64,
+        )
+    }
+
+    /// Renders the map into the area, repainting it with `paint` if it is out of date.
+    ///
+    /// The `selection`, `graticule` and `viewport` arguments identify the scene, a change repaints
+    /// the map immediately so that it stays aligned with the objects picked by the mouse.
+    pub fn render<F>(
+        &mut self,
+        area: Rect,
+        buf: &mut Buffer,
+        viewport: &Viewport,
+        selection: &[usize],
+        graticule: bool,
+        paint: F,
+    ) where
+        F: Fn(&mut Context),
+    {
+        if area.is_empty() {
+            return;
+        }
+
+        let key = (area, selection.to_vec(), graticule, *viewport);
+        let now = Instant::now();
+        if self.protocol.is_none()
+            || self.key.as_ref() != Some(&key)
+            || now.duration_since(self.last_update) >= Self::UPDATE_INTERVAL
+        {
+            let image = self.paint(area, viewport, paint);
+            self.protocol = Some(
+                self.picker
+                    .new_resize_protocol(DynamicImage::ImageRgba8(image)),
+            );
+            self.key = Some(key);
+            self.last_update = now;
+            self.repainted = true;
+        }
+
+        if let Some(protocol) = &mut self.protocol {
+            StatefulImage::default()
+                .resize(Resize::Fit(None))
+                .render(area, buf, protocol);
+        }
+    }
+
+    /// Draws the text written to the overlay since [`Self::overlay_buffer`] over the image
+    /// rendered into the area.
+    ///
+    /// Terminals only draw the image again when the cells holding it change, so text that moves
+    /// away would stay on top of it. Whenever the overlay moves or the image is repainted, every
+    /// drawn cell is therefore marked with a new generation, which sends the image again followed
+    /// by the text.
+    pub fn render_overlay(&mut self, area: Rect, buf: &mut Buffer, overlay: &Buffer) {
+        let mut cells = Vec::new();
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let position = Position::new(x, y);
+                // Cells holding the image data must stay, and so must the cells after them, as
+                // the cursor is moved around while the image is drawn
+                let holds_image = |x| !buf[(x, y)].skip;
+                if Self::is_transparent(&overlay[position])
+                    || holds_image(x)
+                    || (x > area.left() && holds_image(x - 1))
+                {
+                    continue;
+                }
+                cells.push(position);
+            }
+        }
+        if std::mem::take(&mut self.repainted) || cells != self.overlay {
+            self.overlay = cells;
+            self.generation = !self.generation;
+        }
+        for &position in &self.overlay {
+            buf[position] = overlay[position].clone();
+        }
+
+        // The underline colour is invisible without an underline
+        let generation = if self.generation {
+            Color::Reset
+        } else {
+            Color::Black
+        };
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = &mut buf[(x, y)];
+                if !cell.skip {
+                    cell.underline_color = generation;
+                }
+            }
+        }
+    }
+
+    /// Returns whether the image shows through the overlay cell, which it does where nothing was
+    /// written and where shapes were drawn, as the image already shows them in more detail.
+    fn is_transparent(cell: &Cell) -> bool {
+        let symbol = cell.symbol();
+        symbol == Self::TRANSPARENT
+            || symbol
+                .chars()
+                .all(|c| ('\u{2800}'..='\u{28FF}').contains(&c))
+    }
+
+    /// Returns the size of the offscreen canvas in cells.
+    fn canvas_size(&self, area: Rect) -> (u16, u16) {
+        let (font_width, font_height) = self.picker.font_size();
+        let width = (area.width as u32 * font_width as u32).div_ceil(2 * Self::DOT_SIZE);
+        let height = (area.height as u32 * font_height as u32).div_ceil(4 * Self::DOT_SIZE);
+        (width.max(1) as u16, height.max(1) as u16)
+    }
+
+    /// Paints the canvas offscreen and converts it to an image filling the area.
+    fn paint<F>(&self, area: Rect, viewport: &Viewport, paint: F) -> RgbaImage
+    where
+        F: Fn(&mut Context),
+    {
+        let (width, height) = self.canvas_size(area);
+        let canvas_area = Rect::new(0, 0, width, height);
+        let mut canvas_buf = Buffer::empty(canvas_area);
+        for x_bounds in viewport.canvas_x_bounds() {
+            Canvas::default()
+                .marker(Marker::Braille)
+                .paint(&paint)
+                .x_bounds(x_bounds)
+                .y_bounds(viewport.y_bounds())
+                .render(canvas_area, &mut canvas_buf);
+        }
+
+        let (font_width, font_height) = self.picker.font_size();
+        let mut image = RgbaImage::new(
+            area.width as u32 * font_width as u32,
+            area.height as u32 * font_height as u32,
+        );
+        for y in 0..height {
+            for x in 0..width {
+                let cell = &canvas_buf[(x, y)];
+                let Some(dots) = cell
+                    .symbol()
+                    .chars()
+                    .next()
+                    .and_then(|c| (c as u32).checked_sub(0x2800))
+                    .filter(|&dots| dots <= 0xFF)
+                else {
+                    continue;
+                };
+                let color = to_rgba(cell.fg);
+                for (bit, (dx, dy)) in BRAILLE_DOTS.iter().enumerate() {
+                    if dots & (1 << bit) == 0 {
+                        continue;
+                    }
+                    let dot_x = (x as u32 * 2 + dx) * Self::DOT_SIZE;
+                    let dot_y = (y as u32 * 4 + dy) * Self::DOT_SIZE;
+                    for py in dot_y..(dot_y + Self::DOT_SIZE).min(image.height()) {
+                        for px in dot_x..(dot_x + Self::DOT_SIZE).min(image.width()) {
+                            image.put_pixel(px, py, color);
+                        }
+                    }
+                }
+            }
+        }
+        image
+    }
+}
+
+/// Column and row of the dot encoded by each bit of a braille pattern.
+const BRAILLE_DOTS: [(u32, u32); 8] = [
+    (0, 0),
+    (0, 1),
+    (0, 2),
+    (1, 0),
+    (1, 1),
+    (1, 2),
+    (0, 3),
+    (1, 3),
+];
+
+/// Converts a terminal colour to a pixel, using the xterm default palette.
+fn to_rgba(color: Color) -> Rgba<u8> {
+    let [r, g, b] = match color {
+        Color::Rgb(r, g, b) => [r, g, b],
+        Color::Black => [0, 0, 0],
+        Color::Red => [205, 0, 0],
+        Color::Green => [0, 205, 0],
+        Color::Yellow => [205, 205, 0],
+        Color::Blue => [0, 0, 238],
+        Color::Magenta => [205, 0, 205],
+        Color::Cyan => [0, 205, 205],
+        Color::Gray => [229, 229, 229],
+        Color::DarkGray => [127, 127, 127],
+        Color::LightRed => [255, 0, 0],
+        Color::LightGreen => [0, 255, 0],
+        Color::LightYellow => [255, 255, 0],
+        Color::LightBlue => [92, 92, 255],
+        Color::LightMagenta => [255, 0, 255],
+        Color::LightCyan => [0, 255, 255],
+        Color::White | Color::Reset | Color::Indexed(_) => [255, 255, 255],
+    };
+    Rgba([r, g, b, 255])
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+/// Index of an object and its fractional cell position.
+type Entry = (usize, (f64, f64));
+
+/// Grid of object positions in screen space, used to find the object under the mouse cursor.
+#[derive(Default)]
+pub struct SpatialIndex {
+    /// Number of cells spanning 360° of longitude, for wrapping around the international date line.
+    width: u16,
+    /// Indices and fractional cell positions of the objects, bucketed by cell.
+    cells: HashMap<(u16, u16), Vec<Entry>>,
+}
+
+impl SpatialIndex {
+    /// Constructs a new, empty instance of [`SpatialIndex`] where `width` cells span 360° of
+    /// longitude.
+    pub fn new(width: u16) -> Self {
+        Self {
+            width,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Inserts the object at the given fractional cell position.
+    pub fn insert(&mut self, index: usize, position: (f64, f64)) {
+        let cell = (position.0 as u16, position.1 as u16);
+        self.cells.entry(cell).or_default().push((index, position));
+    }
+
+    /// Returns the index of the nearest object within `radius` cells of the given cell.
+    ///
+    /// Cells are about twice as tall as they are wide, so vertical distances are doubled.
+    pub fn nearest(&self, x: u16, y: u16, radius: f64) -> Option<usize> {
+        let center = (x as f64 + 0.5, y as f64 + 0.5);
+        let reach = radius.ceil() as i32;
+
+        let mut nearest: Option<(f64, usize)> = None;
+        for dy in -(reach / 2 + 1)..=(reach / 2 + 1) {
+            for dx in -reach..=reach {
+                let Some(cell) = self.wrap(x as i32 + dx, y as i32 + dy) else {
+                    continue;
+                };
+                for &(index, (object_x, object_y)) in self.cells.get(&cell).into_iter().flatten() {
+                    let distance_x = (object_x - center.0).abs();
+                    let distance_x = distance_x.min(self.width as f64 - distance_x);
+                    let distance_y = (object_y - center.1) * 2.0;
+                    let distance = distance_x.hypot(distance_y);
+                    if distance <= radius && nearest.is_none_or(|(min, _)| distance < min) {
+                        nearest = Some((distance, index));
+                    }
+                }
+            }
+        }
+        nearest.map(|(_, index)| index)
+    }
+
+    /// Wraps the cell around horizontally, returns `None` if it lies above or below the area.
+    fn wrap(&self, x: i32, y: i32) -> Option<(u16, u16)> {
+        if y < 0 || self.width == 0 {
+            return None;
+        }
+        Some((x.rem_euclid(self.width as i32) as u16, y as u16))
+    }
+}
@@ -0,0 +1,205 @@
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use ratatui::style::Color;
+
+use crate::{
+    celestial_body::CelestialBody,
+    object::{lat_lon_alt_to_ecef, Object, State},
+};
+
+/// Predicted ground track of an object over one orbital period.
+pub struct Trajectory {
+    /// The NORAD ID of the object.
+    norad_id: u64,
+    /// The epoch of the elements the trajectory was predicted from.
+    epoch: DateTime<Utc>,
+    /// The time of the first point, truncated to the minute.
+    start: DateTime<Utc>,
+    /// Longitude and latitude of the object, one point per minute.
+    pub points: Vec<(f64, f64)>,
+    /// Events along the trajectory, in chronological order.
+    pub events: Vec<OrbitEvent>,
+}
+
+/// A point of interest along a trajectory.
+pub struct OrbitEvent {
+    pub kind: OrbitEventKind,
+    pub time: DateTime<Utc>,
+    /// Longitude and latitude of the object at the time of the event.
+    pub position: (f64, f64),
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum OrbitEventKind {
+    /// Crossing of the equator northwards.
+    AscendingNode,
+    /// Crossing of the equator southwards.
+    DescendingNode,
+    /// Point of the orbit closest to the earth.
+    Perigee,
+    /// Point of the orbit farthest from the earth.
+    Apogee,
+    /// Entry into the earth's shadow.
+    EclipseEntry,
+    /// Exit from the earth's shadow.
+    EclipseExit,
+}
+
+impl OrbitEventKind {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::AscendingNode => "☊",
+            Self::DescendingNode => "☋",
+            Self::Perigee => "P",
+            Self::Apogee => "A",
+            Self::EclipseEntry => "●",
+            Self::EclipseExit => "○",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Self::AscendingNode | Self::DescendingNode => Color::LightCyan,
+            Self::Perigee | Self::Apogee => Color::LightMagenta,
+            Self::EclipseEntry => Color::DarkGray,
+            Self::EclipseExit => Color::Yellow,
+        }
+    }
+}
+
+impl Trajectory {
+    /// Predicts the trajectory of the object starting at the given time.
+    pub fn predict(object: &Object, time: DateTime<Utc>) -> Self {
+        let start = Self::start(time);
+
+        // Calculate future positions along the trajectory
+        let mut points = Vec::new();
+        let mut events = Vec::new();
+        let mut previous: Option<(DateTime<Utc>, State)> = None;
+        for minutes in 1..object.orbital_period().num_minutes() {
+            let time = start + Duration::minutes(minutes);
+            let state = object.predict(time).unwrap();
+            points.push((state.longitude(), state.latitude()));
+
+            if let Some((previous_time, previous_state)) = &previous {
+                events.extend(find_events(
+                    object,
+                    (*previous_time, previous_state),
+                    (time, &state),
+                ));
+            }
+            previous = Some((time, state));
+        }
+
+        // Apsides, from the mean anomaly
+        let minutes_per_degree = 24.0 * 60.0 / (object.mean_motion() * 360.0);
+        let first = start + Duration::minutes(1);
+        let mean_anomaly = object.mean_anomaly()
+            + (first - object.epoch()).num_seconds() as f64 / 60.0 / minutes_per_degree;
+        for (kind, anomaly) in [
+            (OrbitEventKind::Perigee, 360.0),
+            (OrbitEventKind::Apogee, 180.0),
+        ] {
+            let minutes = (anomaly - mean_anomaly).rem_euclid(360.0) * minutes_per_degree;
+            let time = first + Duration::seconds((minutes * 60.0) as i64);
+            if time < start + Duration::minutes(points.len() as i64) {
+                events.push(event_at(object, kind, time));
+            }
+        }
+        events.sort_by_key(|event| event.time);
+
+        Self {
+            norad_id: object.norad_id(),
+            epoch: object.epoch(),
+            start,
+            points,
+            events,
+        }
+    }
+
+    /// Returns `true` if the trajectory was predicted for the object from the same elements and
+    /// still starts at the given time.
+    pub fn is_valid(&self, object: &Object, time: DateTime<Utc>) -> bool {
+        self.norad_id == object.norad_id()
+            && self.epoch == object.epoch()
+            && self.start == Self::start(time)
+    }
+
+    fn start(time: DateTime<Utc>) -> DateTime<Utc> {
+        time.duration_trunc(Duration::minutes(1)).unwrap()
+    }
+}
+
+/// Finds the node crossings and eclipse transitions between two consecutive points.
+fn find_events(
+    object: &Object,
+    (start_time, start): (DateTime<Utc>, &State),
+    (end_time, end): (DateTime<Utc>, &State),
+) -> Vec<OrbitEvent> {
+    let mut events = Vec::new();
+
+    if start.latitude().signum() != end.latitude().signum() {
+        // Interpolate the time the latitude is zero
+        let fraction = start.latitude() / (start.latitude() - end.latitude());
+        let time = start_time
+            + Duration::milliseconds(
+                ((end_time - start_time).num_milliseconds() as f64 * fraction) as i64,
+            );
+        let kind = if end.latitude() > start.latitude() {
+            OrbitEventKind::AscendingNode
+        } else {
+            OrbitEventKind::DescendingNode
+        };
+        events.push(event_at(object, kind, time));
+    }
+
+    let (start_eclipsed, end_eclipsed) =
+        (is_eclipsed(start, start_time), is_eclipsed(end, end_time));
+    if start_eclipsed != end_eclipsed {
+        // Bisect to about one second
+        let (mut low, mut high) = (start_time, end_time);
+        while high - low > Duration::seconds(1) {
+            let middle = low + (high - low) / 2;
+            let state = object.predict(middle).unwrap();
+            if is_eclipsed(&state, middle) == start_eclipsed {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        let kind = if end_eclipsed {
+            OrbitEventKind::EclipseEntry
+        } else {
+            OrbitEventKind::EclipseExit
+        };
+        events.push(event_at(object, kind, high));
+    }
+
+    events
+}
+
+fn event_at(object: &Object, kind: OrbitEventKind, time: DateTime<Utc>) -> OrbitEvent {
+    let state = object.predict(time).unwrap();
+    OrbitEvent {
+        kind,
+        time,
+        position: (state.longitude(), state.latitude()),
+    }
+}
+
+/// Returns `true` if the object is in the earth's shadow, assuming a cylindrical shadow.
+fn is_eclipsed(state: &State, time: DateTime<Utc>) -> bool {
+    const EARTH_RADIUS: f64 = 6378.137; // WGS84 Earth semi-major axis (km)
+
+    let position = lat_lon_alt_to_ecef([state.latitude(), state.longitude(), state.altitude()]);
+    let sun = CelestialBody::Sun.position(time);
+    let sun_distance = (sun[0].powi(2) + sun[1].powi(2) + sun[2].powi(2)).sqrt();
+    let sun_direction = sun.map(|x| x / sun_distance);
+
+    // Distance along the Sun direction and from the earth-Sun line
+    let along: f64 = (0..3).map(|i| position[i] * sun_direction[i]).sum();
+    let across = (0..3)
+        .map(|i| (position[i] - along * sun_direction[i]).powi(2))
+        .sum::<f64>()
+        .sqrt();
+    along < 0.0 && across < EARTH_RADIUS
+}
@@ -0,0 +1,178 @@
+use ratatui::layout::Rect;
+
+/// The region of the world shown on the map.
+///
+/// The viewport may extend past the international date line, in which case the map wraps around.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Viewport {
+    /// Longitude and latitude of the centre of the viewport in deg.
+    center: (f64, f64),
+    /// Magnification relative to the whole world.
+    zoom: f64,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self {
+            center: (0.0, 0.0),
+            zoom: 1.0,
+        }
+    }
+}
+
+impl Viewport {
+    pub const MIN_ZOOM: f64 = 1.0;
+    pub const MAX_ZOOM: f64 = 32.0;
+
+    pub fn zoom(&self) -> f64 {
+        self.zoom
+    }
+
+    /// Doubles the magnification, up to [`Viewport::MAX_ZOOM`].
+    pub fn zoom_in(&mut self) {
+        self.zoom = (self.zoom * 2.0).min(Self::MAX_ZOOM);
+    }
+
+    /// Halves the magnification, down to [`Viewport::MIN_ZOOM`].
+    pub fn zoom_out(&mut self) {
+        self.zoom = (self.zoom / 2.0).max(Self::MIN_ZOOM);
+    }
+
+    /// Centres the viewport on the given longitude and latitude.
+    ///
+    /// Near the poles the viewport stops short of them, leaving the point off-centre.
+    pub fn center_on(&mut self, lon: f64, lat: f64) {
+        self.center = (lon, lat);
+    }
+
+    /// Moves the viewport eastwards by `dx` of its width and northwards by `dy` of its height.
+    pub fn pan(&mut self, dx: f64, dy: f64) {
+        let half_height = 90.0 / self.zoom;
+        let lon = (self.center.0 + dx * 360.0 / self.zoom + 180.0).rem_euclid(360.0) - 180.0;
+        let lat =
+            (self.center.1 + dy * 180.0 / self.zoom).clamp(-90.0 + half_height, 90.0 - half_height);
+        self.center = (lon, lat);
+    }
+
+    /// Returns the longitudes of the left and right edges.
+    ///
+    /// The left edge is always within [-180°, 180°), the right edge may exceed 180°.
+    pub fn x_bounds(&self) -> [f64; 2] {
+        let half_width = 180.0 / self.zoom;
+        let left = (self.center.0 - half_width + 180.0).rem_euclid(360.0) - 180.0;
+        [left, left + 2.0 * half_width]
+    }
+
+    /// Returns the latitudes of the bottom and top edges.
+    pub fn y_bounds(&self) -> [f64; 2] {
+        let half_height = 90.0 / self.zoom;
+        let center = self.center.1.clamp(-90.0 + half_height, 90.0 - half_height);
+        [center - half_height, center + half_height]
+    }
+
+    /// Returns the canvas x bounds of each copy of the world overlapping the viewport.
+    ///
+    /// Drawing with each of them places shapes given in [-180°, 180°] at every position the
+    /// viewport shows them.
+    pub fn canvas_x_bounds(&self) -> Vec<[f64; 2]> {
+        let [left, right] = self.x_bounds();
+        [0.0, 360.0]
+            .into_iter()
+            .filter(|offset| -180.0 + offset < right)
+            .map(|offset| [left - offset, right - offset])
+            .collect()
+    }
+
+    /// Clips the line between the given lon/lat points to each copy of the world overlapping the
+    /// viewport, returning the visible pieces.
+    ///
+    /// The canvas skips lines with an end outside its bounds, so lines must be clipped first.
+    pub fn clip_line(&self, start: (f64, f64), end: (f64, f64)) -> Vec<[(f64, f64); 2]> {
+        let [bottom, top] = self.y_bounds();
+        self.canvas_x_bounds()
+            .into_iter()
+            .filter_map(|[left, right]| {
+                // Liang-Barsky clipping
+                let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+                let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+                for (p, q) in [
+                    (-dx, start.0 - left),
+                    (dx, right - start.0),
+                    (-dy, start.1 - bottom),
+                    (dy, top - start.1),
+                ] {
+                    if p == 0.0 {
+                        if q < 0.0 {
+                            return None;
+                        }
+                    } else if p < 0.0 {
+                        t0 = t0.max(q / p);
+                    } else {
+                        t1 = t1.min(q / p);
+                    }
+                }
+                if t0 > t1 {
+                    return None;
+                }
+                let point = |t: f64| {
+                    (
+                        (start.0 + t * dx).clamp(left, right),
+                        (start.1 + t * dy).clamp(bottom, top),
+                    )
+                };
+                Some([point(t0), point(t1)])
+            })
+            .collect()
+    }
+
+    /// Returns the number of cells spanning 360° of longitude in the area.
+    pub fn wrap_width(&self, area: Rect) -> u16 {
+        (area.width.saturating_sub(1) as f64 * self.zoom).round() as u16
+    }
+
+    /// Converts area coordinates to lon/lat coordinates.
+    ///
+    /// Returns the coordinates of the center of the cell.
+    pub fn area_to_lon_lat(&self, x: u16, y: u16, area: Rect) -> (f64, f64) {
+        debug_assert!(x < area.width && y < area.height);
+
+        let [left, right] = self.x_bounds();
+        let [bottom, top] = self.y_bounds();
+        // Inverse of the mapping the canvas uses for labels
+        let lon =
+            left + (x as f64 + 0.5) * (right - left) / area.width.saturating_sub(1).max(1) as f64;
+        let lat =
+            top - (y as f64 + 0.5) * (top - bottom) / area.height.saturating_sub(1).max(1) as f64;
+        let lon = (lon.min(right) + 180.0).rem_euclid(360.0) - 180.0;
+        (lon, lat.max(bottom))
+    }
+
+    /// Converts lon/lat coordinates to area coordinates.
+    ///
+    /// Returns `None` if the coordinates are outside the viewport.
+    pub fn lon_lat_to_area(&self, lon: f64, lat: f64, area: Rect) -> Option<(u16, u16)> {
+        let (x, y) = self.lon_lat_to_area_fractional(lon, lat, area)?;
+        Some((x as u16, y as u16))
+    }
+
+    /// Converts lon/lat coordinates to fractional area coordinates.
+    ///
+    /// Returns `None` if the coordinates are outside the viewport.
+    pub fn lon_lat_to_area_fractional(&self, lon: f64, lat: f64, area: Rect) -> Option<(f64, f64)> {
+        debug_assert!((-180.0..=180.0).contains(&lon));
+        debug_assert!((-90.0..=90.0).contains(&lat));
+
+        let [left, right] = self.x_bounds();
+        let [bottom, top] = self.y_bounds();
+        // Bring the longitude into the same turn as the viewport
+        let lon = left + (lon - left).rem_euclid(360.0);
+        if lon > right || !(bottom..=top).contains(&lat) {
+            return None;
+        }
+
+        // Same mapping as the canvas uses for labels
+        let x = (lon - left) * area.width.saturating_sub(1) as f64 / (right - left);
+        let y = (top - lat) * area.height.saturating_sub(1) as f64 / (top - bottom);
+        Some((x, y))
+    }
+}